@@ -0,0 +1,78 @@
+use glam::{Vec2, vec2};
+use crate::constants::MIN_SPEED;
+use crate::colors::Color;
+use crate::config::BallDefinition;
+use crate::pocket::Pocket;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ball {
+    pub pos: Vec2,
+    pub vel: Vec2,
+    pub radius: f32,
+    pub color: Color,
+    pub number: u8,
+    pub is_striped: bool,
+    pub show_number: bool,
+    pub in_pocket: bool,
+}
+
+impl Ball {
+    pub fn new(x: f32, y: f32, color: Color, number: u8, is_striped: bool, radius: f32) -> Self {
+        Ball {
+            pos: vec2(x, y),
+            vel: Vec2::ZERO,
+            radius,
+            color,
+            number,
+            is_striped,
+            show_number: true,
+            in_pocket: false,
+        }
+    }
+
+    pub fn from_definition(pos: Vec2, def: &BallDefinition, radius: f32) -> Self {
+        Ball {
+            show_number: def.show_number,
+            ..Ball::new(pos.x, pos.y, def.color, def.number, def.is_striped, radius)
+        }
+    }
+
+    pub fn update_position(&mut self, friction: f32) {
+        if self.in_pocket { return; }
+        self.pos += self.vel;
+        self.vel *= friction;
+        if self.vel.length_squared() < MIN_SPEED * MIN_SPEED {
+            self.vel = Vec2::ZERO;
+        }
+    }
+
+    // Retorna true se a bola bateu em alguma tabela neste frame. Na boca de uma
+    // caçapa a tabela não existe: a bola segue para dentro.
+    pub fn check_wall_collision(&mut self, table_width: f32, table_height: f32, elasticity: f32, pockets: &[Pocket]) -> bool {
+        if self.in_pocket { return false; }
+        let pos = self.pos;
+        let open = |rail: Vec2| pockets.iter().any(|pocket| pocket.opens(rail, pos));
+        let mut hit = false;
+        if self.pos.x + self.radius > table_width && !open(vec2(1.0, 0.0)) {
+            self.pos.x = table_width - self.radius;
+            self.vel.x *= -elasticity;
+            hit = true;
+        } else if self.pos.x - self.radius < 0.0 && !open(vec2(-1.0, 0.0)) {
+            self.pos.x = self.radius;
+            self.vel.x *= -elasticity;
+            hit = true;
+        }
+        if self.pos.y + self.radius > table_height && !open(vec2(0.0, 1.0)) {
+            self.pos.y = table_height - self.radius;
+            self.vel.y *= -elasticity;
+            hit = true;
+        } else if self.pos.y - self.radius < 0.0 && !open(vec2(0.0, -1.0)) {
+            self.pos.y = self.radius;
+            self.vel.y *= -elasticity;
+            hit = true;
+        }
+        hit
+    }
+}
+
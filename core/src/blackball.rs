@@ -0,0 +1,142 @@
+use crate::Game;
+use crate::ball::Ball;
+use crate::colors::ball_colors;
+use crate::types::{Foul, GameState, PlayerGroup};
+use crate::notification::{LossReason, Notification};
+
+// Disposição do triângulo inglês, linha a linha a partir do ápice:
+// vermelhas (1 a 7), preta no centro (8) e amarelas (9 a 15)
+const BLACKBALL_RACK: [u8; 15] = [1, 9, 2, 3, 8, 10, 11, 4, 12, 5, 6, 13, 14, 7, 15];
+
+impl Game {
+    pub fn setup_blackball_balls(&mut self) {
+        self.balls.clear();
+        let r = self.ball_radius;
+        let head_spot = self.head_spot();
+        self.balls.push(Ball::new(head_spot.x, head_spot.y, ball_colors::CUE, 0, false, r));
+        self.cue_ball_idx = Some(0);
+
+        for (pos, number) in self.rack_positions().into_iter().zip(BLACKBALL_RACK) {
+            let def = &self.ball_definitions_map[&number];
+            self.balls.push(Ball::from_definition(pos, def, r));
+        }
+    }
+
+    fn blackball_foul(&self, group: PlayerGroup, free_shot: bool, cue_ball_is_pocketed: bool) -> Option<Foul> {
+        let first_contact = self.shot_log.first_contact(0);
+        let is_own_ball = |number: u8| match group {
+            PlayerGroup::Undecided => number != 8,
+            PlayerGroup::EightBall => number == 8,
+            _ => self.ball_group(number) == Some(group),
+        };
+
+        if cue_ball_is_pocketed {
+            return Some(Foul::Scratch);
+        }
+        let Some(first_contact) = first_contact else {
+            return Some(Foul::NoContact);
+        };
+        // Na jogada livre vale tocar e encaçapar qualquer bola
+        if !free_shot {
+            if !is_own_ball(first_contact) {
+                return Some(Foul::WrongBallFirst);
+            }
+            let opponent_group = self.opposite_group(group);
+            if group != PlayerGroup::Undecided
+                && self.potted_ball_numbers_this_turn.iter().any(|&number| self.ball_group(number) == Some(opponent_group))
+            {
+                return Some(Foul::WrongBallPotted);
+            }
+        }
+        if self.potted_ball_numbers_this_turn.is_empty() && !self.shot_log.cushion_after_first_contact(0) {
+            return Some(Foul::NoRail);
+        }
+        None
+    }
+
+    // Bola 8 inglesa: sem grupos na saída, duas tacadas para o adversário depois de
+    // uma falta (a primeira livre) e derrota ao encaçapar a preta de forma irregular.
+    pub fn handle_blackball_turn_end(&mut self) {
+        let cue_ball_is_pocketed = self.cue_ball_idx.is_none_or(|idx| self.balls[idx].in_pocket);
+        let shooter = self.current_player;
+        let group = self.player(shooter).group;
+        let was_break_shot = self.is_break_shot;
+        self.is_break_shot = false;
+        let free_shot = self.free_shot;
+        self.free_shot = false;
+
+        let foul = self.blackball_foul(group, free_shot, cue_ball_is_pocketed);
+        self.shot_foul = foul;
+
+        if self.potted_ball_numbers_this_turn.contains(&8) {
+            if was_break_shot {
+                // Preta na saída, com ou sem falta: arma de novo e o mesmo jogador sai
+                self.setup_blackball_balls();
+                self.clear_pocketed_balls();
+                self.is_break_shot = true;
+                self.set_state(GameState::Aiming);
+                self.notify(Notification::BlackOnBreak { breaker: shooter });
+                return;
+            }
+            let on_the_black = group == PlayerGroup::EightBall
+                || (group != PlayerGroup::Undecided && self.balls_left_in_group(group) == 0);
+            let winner = if foul.is_none() && on_the_black {
+                shooter
+            } else {
+                self.notify(Notification::Lost { player: shooter, reason: LossReason::BlackIllegal });
+                self.next_player()
+            };
+            self.notify(Notification::Won { player: winner });
+            self.end_rack(winner);
+            return;
+        }
+
+        if let Some(foul) = foul {
+            self.notify(Notification::Foul { player: shooter, reason: foul });
+            self.advance_turn();
+            self.extra_visit = true;
+            self.free_shot = true;
+            self.notify(Notification::TwoShots { player: self.current_player });
+            if foul == Foul::Scratch {
+                self.set_state(GameState::RepositionCueBall);
+                self.notify_ball_in_hand();
+            } else {
+                self.set_state(GameState::Aiming);
+            }
+            self.update_player_groups_if_only_eight_ball_left();
+            return;
+        }
+
+        let first_potted_group = self.potted_ball_numbers_this_turn.first().and_then(|&number| self.ball_group(number));
+        let mut player_continues_turn = false;
+
+        if group == PlayerGroup::Undecided {
+            if let Some(potted_group) = first_potted_group.filter(|_| !was_break_shot) {
+                self.assign_groups(shooter, potted_group);
+                self.notify(Notification::GroupAssigned { player: shooter, group: potted_group });
+                player_continues_turn = true;
+            } else if first_potted_group.is_some() {
+                self.notify(Notification::OpenTable { after_break: was_break_shot });
+                player_continues_turn = true;
+            }
+        } else if self.potted_ball_numbers_this_turn.iter().any(|&number| self.ball_group(number) == Some(group))
+            || (free_shot && first_potted_group.is_some())
+        {
+            self.notify(Notification::GoodShot);
+            player_continues_turn = true;
+        }
+
+        if player_continues_turn {
+            self.notify(Notification::PlaysAgain { player: shooter });
+        } else if self.extra_visit {
+            self.extra_visit = false;
+            self.notify(Notification::SecondVisit { player: shooter });
+        } else {
+            self.advance_turn();
+            self.notify(Notification::TurnChanged { player: self.current_player });
+        }
+
+        self.set_state(GameState::Aiming);
+        self.update_player_groups_if_only_eight_ball_left();
+    }
+}
@@ -0,0 +1,64 @@
+use glam::vec2;
+use crate::Game;
+use crate::ball::Ball;
+use crate::colors::ball_colors;
+use crate::config::{CAROM_YELLOW, CAROM_RED};
+use crate::constants::THREE_CUSHION_MIN_CUSHIONS;
+use crate::types::GameState;
+use crate::notification::Notification;
+
+impl Game {
+    // Posição de saída: vermelha no foot spot, amarela no head spot e a branca
+    // de quem sai no ponto `break` da mesa (sem ele, 152 mm ao lado do head spot)
+    pub fn setup_carom_balls(&mut self) {
+        self.balls.clear();
+        let r = self.ball_radius;
+        let head_spot = self.head_spot();
+        let foot_spot = self.foot_spot();
+        let break_spot = self.table_spot("break").unwrap_or(head_spot + vec2(0.0, 152.0 * self.table_scale()));
+
+        self.balls.push(Ball::new(break_spot.x, break_spot.y, ball_colors::CUE, 0, false, r));
+        for (pos, number) in [(head_spot, CAROM_YELLOW), (foot_spot, CAROM_RED)] {
+            let def = &self.ball_definitions_map[&number];
+            self.balls.push(Ball::from_definition(pos, def, r));
+        }
+
+        // A bola da posição de saída é de quem sai; a do head spot, do adversário
+        let breaker = self.current_player;
+        for id in self.player_ids() {
+            self.player_mut(id).cue_ball = if id == breaker { 0 } else { CAROM_YELLOW };
+        }
+        self.sync_cue_ball_to_current_player();
+    }
+
+    // Três tabelas: ponto quando a bola do jogador toca as outras duas e bate em
+    // pelo menos três tabelas antes de tocar a segunda. Quem pontua continua.
+    pub fn handle_three_cushion_turn_end(&mut self) {
+        let shooter = self.current_player;
+        self.is_break_shot = false;
+
+        let striker = self.current().cue_ball;
+        let scored = self.shot_log
+            .cushions_before_second_contact(striker)
+            .is_some_and(|cushions| cushions >= THREE_CUSHION_MIN_CUSHIONS);
+
+        if scored {
+            self.current_mut().score += 1;
+            let score = self.current().score;
+            self.notify(Notification::CaromPoint { player: shooter, score });
+            if score >= self.target_score {
+                self.notify(Notification::Won { player: shooter });
+                self.end_rack(shooter);
+                return;
+            }
+            self.notify(Notification::PlaysAgain { player: shooter });
+        } else {
+            self.advance_turn();
+            self.sync_cue_ball_to_current_player();
+            self.notify(Notification::NoPoints);
+            self.notify(Notification::TurnChanged { player: self.current_player });
+        }
+
+        self.set_state(GameState::Aiming);
+    }
+}
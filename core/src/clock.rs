@@ -0,0 +1,189 @@
+use crate::Game;
+use crate::constants::{
+    CONSECUTIVE_FOUL_LIMIT, SHOT_CLOCK_EXTENSION_SECONDS, STRAIGHT_POOL_FOUL_PENALTY,
+};
+use crate::types::{Foul, GameState, GameVariant, PlacementArea, PlayerId};
+use crate::replay::ReplayInput;
+use crate::notification::{LossReason, Notification};
+
+// Controle de tempo da partida: relógio por tacada (com extensões por rack) e,
+// opcionalmente, tempo total por jogador como no xadrez
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeControl {
+    pub shot_seconds: Option<f32>,
+    pub extensions_per_rack: u32,
+    pub match_seconds: Option<f32>,
+}
+
+impl TimeControl {
+    pub const OFF: TimeControl = TimeControl { shot_seconds: None, extensions_per_rack: 0, match_seconds: None };
+
+    // Opções oferecidas no botão do relógio, em ordem
+    pub const PRESETS: [TimeControl; 4] = [
+        TimeControl::OFF,
+        TimeControl { shot_seconds: Some(30.0), extensions_per_rack: 1, match_seconds: None },
+        TimeControl { shot_seconds: Some(30.0), extensions_per_rack: 1, match_seconds: Some(600.0) },
+        TimeControl { shot_seconds: Some(15.0), extensions_per_rack: 1, match_seconds: Some(180.0) },
+    ];
+
+    pub fn next(&self) -> TimeControl {
+        let idx = Self::PRESETS.iter().position(|preset| preset == self).unwrap_or(0);
+        Self::PRESETS[(idx + 1) % Self::PRESETS.len()]
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.shot_seconds.is_none() && self.match_seconds.is_none()
+    }
+}
+
+// Minutos e segundos, arredondando para cima para o relógio não mostrar 0:00 antes da hora
+pub fn format_clock(seconds: f32) -> String {
+    let total = seconds.max(0.0).ceil() as u32;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+
+impl Game {
+    // Reinicia o relógio da tacada; chamado quando uma tacada sai e no começo do rack
+    pub fn reset_shot_clock(&mut self) {
+        self.shot_time_left = self.time_control.shot_seconds.unwrap_or(0.0);
+    }
+
+    // Extensões e tempo total de cada jogador no começo da partida
+    pub fn reset_player_clocks(&mut self) {
+        let time_control = self.time_control;
+        for player in &mut self.players {
+            player.extensions_left = time_control.extensions_per_rack;
+            player.match_time_left = time_control.match_seconds;
+        }
+        self.reset_shot_clock();
+    }
+
+    pub fn clock_is_running(&self) -> bool {
+        !self.time_control.is_off()
+            && matches!(self.game_state, GameState::Aiming | GameState::RepositionCueBall)
+    }
+
+    pub fn can_use_extension(&self) -> bool {
+        self.clock_is_running()
+            && self.time_control.shot_seconds.is_some()
+            && self.current().extensions_left > 0
+    }
+
+    pub(crate) fn use_extension(&mut self) -> bool {
+        if !self.can_use_extension() { return false; }
+        self.current_mut().extensions_left -= 1;
+        self.record_input(ReplayInput::UseExtension);
+        self.shot_time_left += SHOT_CLOCK_EXTENSION_SECONDS;
+        self.notify(Notification::ExtensionUsed { player: self.current_player, seconds: SHOT_CLOCK_EXTENSION_SECONDS });
+        true
+    }
+
+    // O relógio só anda enquanto o jogador decide a tacada; com bolas em movimento fica parado
+    pub fn update_clock(&mut self, dt: f32) {
+        if !self.clock_is_running() || self.is_lagging() { return; }
+
+        if let Some(time_left) = self.current().match_time_left {
+            let time_left = time_left - dt;
+            self.current_mut().match_time_left = Some(time_left);
+            if time_left <= 0.0 {
+                self.handle_match_time_expired();
+                return;
+            }
+        }
+        if self.time_control.shot_seconds.is_some() {
+            self.shot_time_left -= dt;
+            if self.shot_time_left <= 0.0 {
+                self.handle_shot_clock_expired();
+            }
+        }
+    }
+
+    // Tempo da tacada esgotado: falta da variante, marcada e avisada como as da mesa,
+    // e a vez passa com a bola onde está (a bola na mão continua só para a branca
+    // encaçapada). No bola 8 o adversário ganha a bola na mão como em toda falta.
+    pub(crate) fn handle_shot_clock_expired(&mut self) {
+        self.record_input(ReplayInput::ShotClockExpired);
+        let shooter = self.current_player;
+        let cue_ball_in_hand = self.game_state == GameState::RepositionCueBall;
+        self.notify(Notification::ShotClockExpired { player: shooter });
+
+        // A falta do snooker já passa a vez (ou encerra o frame na preta final)
+        if self.variant == GameVariant::Snooker {
+            let value = self.snooker_foul_value();
+            self.apply_snooker_foul(Foul::ShotClock, value, cue_ball_in_hand);
+            self.reset_shot_clock();
+            return;
+        }
+        self.shot_foul = Some(Foul::ShotClock);
+        self.called_ball = None;
+        self.notify(Notification::Foul { player: shooter, reason: Foul::ShotClock });
+
+        let variant = self.variant;
+        match variant {
+            // Na terceira falta seguida o infrator sai de novo, sem passar a vez
+            GameVariant::StraightPool if self.apply_straight_pool_foul(STRAIGHT_POOL_FOUL_PENALTY) => {
+                self.reset_shot_clock();
+                return;
+            }
+            GameVariant::Blackball => {
+                self.extra_visit = true;
+                self.free_shot = true;
+            }
+            _ => {}
+        }
+
+        let fouls = if self.three_foul_rule() { self.track_foul(shooter, true) } else { 0 };
+        if fouls >= CONSECUTIVE_FOUL_LIMIT && self.eliminate(shooter, LossReason::ThirdFoul) {
+            return;
+        }
+
+        self.advance_turn();
+        self.sync_cue_ball_to_current_player();
+        self.reset_shot_clock();
+        self.notify(Notification::TurnChanged { player: self.current_player });
+        // Na saída a bola na mão é sempre na área de saída
+        if self.variant == GameVariant::EightBall {
+            self.ball_in_hand_area = if self.is_break_shot { PlacementArea::Kitchen } else { self.house_rules.ball_in_hand };
+            self.set_state(GameState::RepositionCueBall);
+            self.notify_ball_in_hand();
+        }
+    }
+
+    // Tempo total esgotado: o jogador perde a partida, e não só o rack. Com mais de
+    // dois ele sai da partida e os outros seguem; start_rack o deixa de fora dos próximos.
+    pub(crate) fn handle_match_time_expired(&mut self) {
+        self.record_input(ReplayInput::MatchTimeExpired);
+        let loser = self.current_player;
+        self.player_mut(loser).eliminated = true;
+        if let Some(winner) = self.last_player_standing() {
+            self.notify(Notification::Lost { player: loser, reason: LossReason::OutOfTime });
+            self.notify(Notification::Won { player: winner });
+            self.end_match(winner);
+            return;
+        }
+        self.notify(Notification::Eliminated { player: loser, reason: LossReason::OutOfTime });
+        self.advance_turn();
+        self.sync_cue_ball_to_current_player();
+        self.reset_shot_clock();
+        self.notify(Notification::TurnChanged { player: self.current_player });
+    }
+
+    // Tira o jogador da partida. Com um único jogador restante o rack acaba, ele
+    // vence e retorna true.
+    pub fn eliminate(&mut self, player: PlayerId, reason: LossReason) -> bool {
+        self.player_mut(player).eliminated = true;
+        match self.last_player_standing() {
+            Some(winner) => {
+                self.notify(Notification::Lost { player, reason });
+                self.notify(Notification::Won { player: winner });
+                self.end_rack(winner);
+                true
+            }
+            None => {
+                self.notify(Notification::Eliminated { player, reason });
+                false
+            }
+        }
+    }
+}
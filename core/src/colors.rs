@@ -0,0 +1,42 @@
+// Cor RGBA própria do núcleo, para não depender do macroquad. O front end converte
+// na hora de desenhar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color { r, g, b, a }
+    }
+}
+
+// Cores das bolas de cada modalidade
+pub mod ball_colors {
+    use super::Color;
+
+    pub const CUE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+    pub const SOLID_YELLOW: Color = Color::new(0.98, 0.80, 0.08, 1.0); // #facc15
+    pub const SOLID_BLUE: Color = Color::new(0.23, 0.51, 0.96, 1.0); // #3b82f6
+    pub const SOLID_RED: Color = Color::new(0.94, 0.27, 0.27, 1.0); // #ef4444
+    pub const SOLID_PURPLE: Color = Color::new(0.66, 0.33, 0.97, 1.0); // #a855f7
+    pub const SOLID_ORANGE: Color = Color::new(0.98, 0.45, 0.09, 1.0); // #f97316
+    pub const SOLID_GREEN: Color = Color::new(0.13, 0.77, 0.35, 1.0); // #22c55e
+    pub const SOLID_MAROON: Color = Color::new(0.53, 0.07, 0.21, 1.0); // #881337
+    pub const EIGHT_BALL: Color = Color::new(0.09, 0.09, 0.09, 1.0); // #171717
+    pub const SNOOKER_RED: Color = Color::new(0.75, 0.08, 0.10, 1.0);
+    pub const SNOOKER_YELLOW: Color = Color::new(0.98, 0.84, 0.10, 1.0);
+    pub const SNOOKER_GREEN: Color = Color::new(0.05, 0.45, 0.20, 1.0);
+    pub const SNOOKER_BROWN: Color = Color::new(0.45, 0.25, 0.10, 1.0);
+    pub const SNOOKER_BLUE: Color = Color::new(0.10, 0.30, 0.85, 1.0);
+    pub const SNOOKER_PINK: Color = Color::new(0.98, 0.55, 0.70, 1.0);
+    pub const SNOOKER_BLACK: Color = Color::new(0.05, 0.05, 0.05, 1.0);
+    pub const BLACKBALL_RED: Color = Color::new(0.80, 0.10, 0.12, 1.0);
+    pub const BLACKBALL_YELLOW: Color = Color::new(0.99, 0.82, 0.05, 1.0);
+    pub const CAROM_YELLOW: Color = Color::new(0.98, 0.90, 0.35, 1.0);
+    pub const CAROM_RED: Color = Color::new(0.85, 0.10, 0.10, 1.0);
+}
@@ -0,0 +1,116 @@
+use crate::colors::{Color, ball_colors};
+use crate::types::GameVariant;
+
+#[derive(Clone, Debug)] // Adicionado Debug para permitir imprimir
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BallDefinition {
+    pub number: u8,
+    pub color: Color,
+    pub is_striped: bool,
+    pub show_number: bool,
+}
+
+pub fn get_ball_definitions() -> Vec<BallDefinition> {
+    vec![
+        BallDefinition { number: 1, color: ball_colors::SOLID_YELLOW, is_striped: false, show_number: true },
+        BallDefinition { number: 2, color: ball_colors::SOLID_BLUE, is_striped: false, show_number: true },
+        BallDefinition { number: 3, color: ball_colors::SOLID_RED, is_striped: false, show_number: true },
+        BallDefinition { number: 4, color: ball_colors::SOLID_PURPLE, is_striped: false, show_number: true },
+        BallDefinition { number: 5, color: ball_colors::SOLID_ORANGE, is_striped: false, show_number: true },
+        BallDefinition { number: 6, color: ball_colors::SOLID_GREEN, is_striped: false, show_number: true },
+        BallDefinition { number: 7, color: ball_colors::SOLID_MAROON, is_striped: false, show_number: true },
+        BallDefinition { number: 8, color: ball_colors::EIGHT_BALL, is_striped: false, show_number: true },
+        BallDefinition { number: 9, color: ball_colors::SOLID_YELLOW, is_striped: true, show_number: true },
+        BallDefinition { number: 10, color: ball_colors::SOLID_BLUE, is_striped: true, show_number: true },
+        BallDefinition { number: 11, color: ball_colors::SOLID_RED, is_striped: true, show_number: true },
+        BallDefinition { number: 12, color: ball_colors::SOLID_PURPLE, is_striped: true, show_number: true },
+        BallDefinition { number: 13, color: ball_colors::SOLID_ORANGE, is_striped: true, show_number: true },
+        BallDefinition { number: 14, color: ball_colors::SOLID_GREEN, is_striped: true, show_number: true },
+        BallDefinition { number: 15, color: ball_colors::SOLID_MAROON, is_striped: true, show_number: true },
+    ]
+}
+
+
+// Identificadores das cores do snooker, em ordem crescente de valor.
+// As vermelhas usam os números 1 a 15.
+pub const SNOOKER_YELLOW: u8 = 16;
+pub const SNOOKER_GREEN: u8 = 17;
+pub const SNOOKER_BROWN: u8 = 18;
+pub const SNOOKER_BLUE: u8 = 19;
+pub const SNOOKER_PINK: u8 = 20;
+pub const SNOOKER_BLACK: u8 = 21;
+pub const SNOOKER_COLOURS: [u8; 6] = [SNOOKER_YELLOW, SNOOKER_GREEN, SNOOKER_BROWN, SNOOKER_BLUE, SNOOKER_PINK, SNOOKER_BLACK];
+
+pub fn is_snooker_red(number: u8) -> bool {
+    (1..=15).contains(&number)
+}
+
+pub fn snooker_ball_value(number: u8) -> i32 {
+    if is_snooker_red(number) {
+        1
+    } else if SNOOKER_COLOURS.contains(&number) {
+        (number - SNOOKER_YELLOW) as i32 + 2
+    } else {
+        0
+    }
+}
+
+pub fn get_snooker_ball_definitions() -> Vec<BallDefinition> {
+    let mut defs: Vec<BallDefinition> = (1..=15)
+        .map(|number| BallDefinition { number, color: ball_colors::SNOOKER_RED, is_striped: false, show_number: false })
+        .collect();
+    defs.extend([
+        BallDefinition { number: SNOOKER_YELLOW, color: ball_colors::SNOOKER_YELLOW, is_striped: false, show_number: false },
+        BallDefinition { number: SNOOKER_GREEN, color: ball_colors::SNOOKER_GREEN, is_striped: false, show_number: false },
+        BallDefinition { number: SNOOKER_BROWN, color: ball_colors::SNOOKER_BROWN, is_striped: false, show_number: false },
+        BallDefinition { number: SNOOKER_BLUE, color: ball_colors::SNOOKER_BLUE, is_striped: false, show_number: false },
+        BallDefinition { number: SNOOKER_PINK, color: ball_colors::SNOOKER_PINK, is_striped: false, show_number: false },
+        BallDefinition { number: SNOOKER_BLACK, color: ball_colors::SNOOKER_BLACK, is_striped: false, show_number: false },
+    ]);
+    defs
+}
+
+// Bola 8 inglesa: vermelhas 1 a 7, preta 8 e amarelas 9 a 15, todas sem número
+pub fn get_blackball_ball_definitions() -> Vec<BallDefinition> {
+    (1..=15)
+        .map(|number| {
+            let color = match number {
+                1..=7 => ball_colors::BLACKBALL_RED,
+                8 => ball_colors::EIGHT_BALL,
+                _ => ball_colors::BLACKBALL_YELLOW,
+            };
+            BallDefinition { number, color, is_striped: false, show_number: false }
+        })
+        .collect()
+}
+
+// Três tabelas: a branca (0) e a amarela são as bolas dos jogadores
+pub const CAROM_YELLOW: u8 = 1;
+pub const CAROM_RED: u8 = 2;
+
+pub fn get_carom_ball_definitions() -> Vec<BallDefinition> {
+    vec![
+        BallDefinition { number: CAROM_YELLOW, color: ball_colors::CAROM_YELLOW, is_striped: false, show_number: false },
+        BallDefinition { number: CAROM_RED, color: ball_colors::CAROM_RED, is_striped: false, show_number: false },
+    ]
+}
+
+pub fn ball_definitions_for(variant: GameVariant) -> Vec<BallDefinition> {
+    match variant {
+        GameVariant::ThreeCushion => get_carom_ball_definitions(),
+        GameVariant::Snooker => get_snooker_ball_definitions(),
+        GameVariant::Blackball => get_blackball_ball_definitions(),
+        _ => get_ball_definitions(),
+    }
+}
+
+// Diâmetro das bolas de cada modalidade, em milímetros; a mesa vem de table.rs
+pub fn ball_diameter(variant: GameVariant) -> f32 {
+    match variant {
+        GameVariant::Snooker => 52.5,
+        // Bola 8 inglesa: bolas de 2 polegadas
+        GameVariant::Blackball => 50.8,
+        GameVariant::ThreeCushion => 61.5,
+        _ => 57.15,
+    }
+}
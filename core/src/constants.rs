@@ -0,0 +1,38 @@
+// Comprimento da área de jogo em unidades da mesa; o resto das medidas sai da
+// especificação da mesa (table.rs), na mesma escala
+pub const TABLE_LENGTH: f32 = 1600.0;
+// Velocidade da branca (unidades por quadro) numa tacada com força máxima
+pub const MAX_SHOT_SPEED: f32 = 54.0;
+pub const MIN_SPEED: f32 = 0.05;
+// Perfil de física padrão (PhysicsProfile::Standard); os outros partem destes
+pub const FRICTION: f32 = 0.98;
+pub const CUSHION_ELASTICITY: f32 = 0.8;
+pub const BALL_ELASTICITY: f32 = 0.95;
+
+// Bola 8: saída válida exige bola encaçapada ou pelo menos quatro bolas numa tabela
+pub const LEGAL_BREAK_MIN_RAIL_BALLS: usize = 4;
+// Faltas seguidas que fazem o jogador perder a partida
+pub const CONSECUTIVE_FOUL_LIMIT: u32 = 3;
+
+// Relógio de tacada
+pub const SHOT_CLOCK_EXTENSION_SECONDS: f32 = 30.0;
+pub const SHOT_CLOCK_WARNING_SECONDS: f32 = 10.0;
+
+// Contínuo (14.1)
+pub const STRAIGHT_POOL_TARGET_SCORE: i32 = 100;
+pub const STRAIGHT_POOL_FOUL_PENALTY: i32 = 1;
+pub const STRAIGHT_POOL_BREAK_FOUL_PENALTY: i32 = 2;
+pub const STRAIGHT_POOL_THREE_FOUL_PENALTY: i32 = 15;
+
+// Três tabelas
+pub const THREE_CUSHION_TARGET_SCORE: i32 = 15;
+pub const THREE_CUSHION_MIN_CUSHIONS: usize = 3;
+
+// Snooker; a posição das bolas coloridas vem da mesa
+pub const SNOOKER_MIN_FOUL_VALUE: i32 = 4;
+
+// Desfazer: tacadas guardadas no histórico (cada uma é uma cópia do Game)
+pub const MAX_UNDO_SHOTS: usize = 100;
+
+// Transições de estado guardadas em Game::state_history (as mais antigas saem)
+pub const MAX_STATE_TRANSITIONS: usize = 256;
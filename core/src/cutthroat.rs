@@ -0,0 +1,98 @@
+use crate::Game;
+use crate::constants::CONSECUTIVE_FOUL_LIMIT;
+use crate::types::{Foul, GameState, PlayerGroup};
+use crate::notification::{LossReason, Notification};
+
+impl Game {
+    // Divide as 15 bolas em faixas seguidas, uma por jogador. Quando a divisão
+    // não é exata os primeiros jogadores ficam com uma bola a mais.
+    pub fn assign_cutthroat_ranges(&mut self) {
+        let count = self.players.len() as u8;
+        let (base, extra) = (15 / count, 15 % count);
+        let mut low = 1;
+        for (i, player) in self.players.iter_mut().enumerate() {
+            let size = base + u8::from((i as u8) < extra);
+            player.group = PlayerGroup::Range(low, low + size - 1);
+            low += size;
+        }
+    }
+
+    pub fn cutthroat_range_of(&self, number: u8) -> Option<PlayerGroup> {
+        self.players.iter()
+            .map(|player| player.group)
+            .find(|group| matches!(group, PlayerGroup::Range(low, high) if (*low..=*high).contains(&number)))
+    }
+
+    fn cutthroat_foul(&self, cue_ball_is_pocketed: bool) -> Option<Foul> {
+        if cue_ball_is_pocketed {
+            return Some(Foul::Scratch);
+        }
+        if self.shot_log.first_contact(0).is_none() {
+            return Some(Foul::NoContact);
+        }
+        if self.potted_ball_numbers_this_turn.is_empty() && !self.shot_log.cushion_after_first_contact(0) {
+            return Some(Foul::NoRail);
+        }
+        None
+    }
+
+    // Tira da partida quem não tem mais bolas na mesa. Retorna true quando sobra
+    // só um jogador e o rack acabou.
+    fn eliminate_cutthroat_players(&mut self) -> bool {
+        for id in self.player_ids() {
+            let player = self.player(id);
+            if !player.eliminated && self.balls_left_in_group(player.group) == 0
+                && self.eliminate(id, LossReason::NoBallsLeft)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    // Cutthroat: cada um defende a própria faixa de bolas e tenta encaçapar as dos
+    // outros. Quem encaçapa continua; vence o último com bolas na mesa.
+    pub fn handle_cutthroat_turn_end(&mut self) {
+        let cue_ball_is_pocketed = self.cue_ball_idx.is_none_or(|idx| self.balls[idx].in_pocket);
+        let shooter = self.current_player;
+        self.is_break_shot = false;
+
+        let foul = self.cutthroat_foul(cue_ball_is_pocketed);
+        self.shot_foul = foul;
+        let fouls = self.track_foul(shooter, foul.is_some());
+        if let Some(foul) = foul {
+            self.notify(Notification::Foul { player: shooter, reason: foul });
+            if fouls == CONSECUTIVE_FOUL_LIMIT - 1 {
+                self.notify(Notification::FoulWarning { player: shooter });
+            }
+        }
+        // Na terceira falta seguida o jogador sai da partida
+        if fouls >= CONSECUTIVE_FOUL_LIMIT && self.eliminate(shooter, LossReason::ThirdFoul) {
+            return;
+        }
+        if self.eliminate_cutthroat_players() {
+            return;
+        }
+
+        let player_continues_turn = foul.is_none()
+            && !self.potted_ball_numbers_this_turn.is_empty()
+            && !self.player(shooter).eliminated;
+        if player_continues_turn {
+            self.notify(Notification::GoodShot);
+        } else {
+            self.advance_turn();
+        }
+
+        if foul == Some(Foul::Scratch) {
+            self.set_state(GameState::RepositionCueBall);
+            self.notify_ball_in_hand();
+        } else {
+            self.set_state(GameState::Aiming);
+            self.notify(if player_continues_turn {
+                Notification::PlaysAgain { player: shooter }
+            } else {
+                Notification::TurnChanged { player: self.current_player }
+            });
+        }
+    }
+}
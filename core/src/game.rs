@@ -0,0 +1,493 @@
+use glam::{Vec2, vec2};
+use std::collections::HashMap;
+
+use crate::types::{Foul, PlayerId, GameState, GameVariant, PlacementArea, SnookerPhase};
+use crate::constants::*; // Importa todas as constantes
+use crate::colors::ball_colors;
+use crate::config::{BallDefinition, ball_definitions_for, ball_diameter};
+use crate::ball::Ball;
+use crate::pocket::Pocket;
+use crate::player::PlayerState;
+use crate::shot::ShotLog;
+use crate::clock::TimeControl;
+use crate::match_play::MatchSettings;
+use crate::house_rules::HouseRules;
+use crate::replay::{Replay, ReplayInput};
+use crate::rack::RackOptions;
+use crate::notification::Notification;
+use crate::state_machine::StateTransition;
+use crate::physics::PhysicsProfile;
+use crate::script::ScriptedVariant;
+use crate::table::TableSpec;
+
+// Estado completo da partida. As medidas são em unidades da mesa (TABLE_LENGTH de
+// comprimento), e quem desenha escala para a tela.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Game {
+    pub ball_radius: f32,
+    pub max_power_shot: f32,
+
+    // Mesa da partida; new_match troca por a padrão quando ela não serve à modalidade
+    pub table: TableSpec,
+    pub table_width: f32,
+    pub table_height: f32,
+
+    pub balls: Vec<Ball>,
+    pub pockets: Vec<Pocket>,
+    pub cue_ball_idx: Option<usize>,
+    // Só muda por set_state, que confere a transição e a guarda no histórico; de fora
+    // se lê por game_state()
+    pub(crate) game_state: GameState,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub state_history: Vec<StateTransition>,
+    // Avisos ainda não lidos pela interface; não vão para o save
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub notifications: Vec<Notification>,
+
+    pub variant: GameVariant,
+    pub shot_log: ShotLog,
+    pub shot_foul: Option<Foul>, // Falta marcada na última tacada
+    pub called_ball: Option<u8>,
+    pub target_score: i32,
+    pub snooker_phase: SnookerPhase,
+    pub free_ball: bool,
+    pub extra_visit: bool,
+    pub free_shot: bool,
+
+    pub player_count: usize,
+    pub players: Vec<PlayerState>,
+    pub current_player: PlayerId,
+    pub potted_ball_numbers_this_turn: Vec<u8>,
+    pub is_break_shot: bool,
+    pub house_rules: HouseRules,
+    pub called_pocket: Option<usize>,
+    pub ball_in_hand_area: PlacementArea,
+    pub time_control: TimeControl,
+    pub shot_time_left: f32,
+
+    pub match_settings: MatchSettings,
+    pub match_score: Vec<u32>,
+    pub breaker: PlayerId,
+    pub rack_winner: Option<PlayerId>,
+    pub lag_results: Option<Vec<Option<f32>>>,
+
+    // Gravação do rack atual para o replay: como ele foi armado e as entradas desde então
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rack_recording: Option<Replay>,
+
+    // Racks sorteados: opções, semente do rack atual e de onde sai a próxima
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rack_options: RackOptions,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rack_seed: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rack_seed_state: u64,
+
+    // Regras da variante com script (GameVariant::Scripted)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub script: Option<ScriptedVariant>,
+
+    // Perfil de física da partida (vem das configurações do jogador)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub physics: PhysicsProfile,
+
+    pub ball_definitions_map: HashMap<u8, BallDefinition>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        let ball_definitions_map = Self::definitions_map(GameVariant::EightBall);
+
+        let mut game = Game {
+            ball_radius: 0.0,
+            max_power_shot: 0.0,
+            table: TableSpec::standard(GameVariant::EightBall),
+            table_width: 0.0,
+            table_height: 0.0,
+            balls: Vec::new(),
+            pockets: Vec::new(),
+            cue_ball_idx: None,
+            game_state: GameState::Initializing,
+            state_history: Vec::new(),
+            notifications: Vec::new(),
+            variant: GameVariant::EightBall,
+            shot_log: ShotLog::default(),
+            shot_foul: None,
+            called_ball: None,
+            target_score: STRAIGHT_POOL_TARGET_SCORE,
+            snooker_phase: SnookerPhase::Red,
+            free_ball: false,
+            extra_visit: false,
+            free_shot: false,
+            player_count: 2,
+            players: Vec::new(),
+            current_player: PlayerId(0),
+            potted_ball_numbers_this_turn: Vec::new(),
+            is_break_shot: true,
+            house_rules: HouseRules::default(),
+            called_pocket: None,
+            ball_in_hand_area: PlacementArea::Kitchen,
+            time_control: TimeControl::OFF,
+            shot_time_left: 0.0,
+            match_settings: MatchSettings::SINGLE_RACK,
+            match_score: Vec::new(),
+            breaker: PlayerId(0),
+            rack_winner: None,
+            lag_results: None,
+            rack_recording: None,
+            rack_options: RackOptions::default(),
+            rack_seed: 0,
+            rack_seed_state: 0,
+            script: None,
+            physics: PhysicsProfile::default(),
+            ball_definitions_map,
+        };
+        game.new_match();
+        game
+    }
+
+    fn definitions_map(variant: GameVariant) -> HashMap<u8, BallDefinition> {
+        ball_definitions_for(variant).into_iter()
+            .map(|def| (def.number, def))
+            .collect::<HashMap<_, _>>()
+    }
+
+    // Nova partida com a modalidade e as opções atuais (variant, player_count,
+    // house_rules, time_control, match_settings)
+    pub fn new_match(&mut self) {
+        self.set_table_dimensions();
+        self.setup_pockets();
+        self.initialize_game_logic();
+    }
+
+    fn set_table_dimensions(&mut self) {
+        if !self.table.fits(self.variant) {
+            self.table = TableSpec::standard(self.variant);
+        }
+        self.table_width = TABLE_LENGTH;
+        self.table_height = TABLE_LENGTH * self.table.width / self.table.length;
+        self.max_power_shot = MAX_SHOT_SPEED;
+        self.ball_radius = ball_diameter(self.variant) / 2.0 * self.table_scale();
+    }
+
+    // Caçapas da mesa, com a direção para fora tirada das tabelas em que cada uma está
+    fn setup_pockets(&mut self) {
+        let scale = self.table_scale();
+        self.pockets = self.table.pockets.iter()
+            .map(|spec| {
+                let side = |pos: f32, end: f32| if pos == 0.0 { -1.0 } else if pos == end { 1.0 } else { 0.0 };
+                let normal = vec2(side(spec.pos.x, self.table.length), side(spec.pos.y, self.table.width)).normalize_or_zero();
+                Pocket::new(spec.pos * scale, normal, spec.mouth * scale, spec.shelf * scale)
+            })
+            .collect();
+    }
+
+    // Unidades da mesa por milímetro da especificação
+    pub fn table_scale(&self) -> f32 {
+        self.table_width / self.table.length
+    }
+
+    // Ponto marcado na mesa, em unidades da mesa
+    pub fn table_spot(&self, name: &str) -> Option<Vec2> {
+        self.table.spot(name).map(|pos| pos * self.table_scale())
+    }
+
+    // Caçapa sob um ponto da mesa, com uma folga para facilitar o toque
+    pub fn pocket_at(&self, pos: Vec2) -> Option<usize> {
+        self.pockets.iter().position(|pocket| pocket.pos.distance(pos) <= pocket.radius * 1.5)
+    }
+
+    pub fn baulk_line_x(&self) -> f32 {
+        self.table.head_string * self.table_scale()
+    }
+
+    pub fn head_spot(&self) -> Vec2 {
+        self.table_spot("head").unwrap_or(vec2(self.baulk_line_x(), self.table_height / 2.0))
+    }
+
+    pub fn cue_ball_placement_area(&self) -> PlacementArea {
+        match self.variant {
+            GameVariant::Snooker => PlacementArea::D,
+            GameVariant::EightBall | GameVariant::Scripted => self.ball_in_hand_area,
+            _ => PlacementArea::Kitchen,
+        }
+    }
+
+    pub fn is_in_cue_ball_area(&self, pos: Vec2) -> bool {
+        let on_table = pos.x > 0.0 && pos.x < self.table_width && pos.y > 0.0 && pos.y < self.table_height;
+        on_table && match self.cue_ball_placement_area() {
+            PlacementArea::Kitchen => pos.x < self.baulk_line_x(),
+            PlacementArea::D => pos.x <= self.baulk_line_x() && pos.distance(self.head_spot()) <= self.snooker_d_radius(),
+            PlacementArea::Anywhere => true,
+        }
+    }
+
+    pub fn center_spot(&self) -> Vec2 {
+        self.table_spot("center").unwrap_or(vec2(self.table_width / 2.0, self.table_height / 2.0))
+    }
+
+    pub fn foot_spot(&self) -> Vec2 {
+        self.table_spot("foot").unwrap_or(vec2(self.table_width * 0.75, self.table_height / 2.0))
+    }
+
+    pub fn player(&self, id: PlayerId) -> &PlayerState {
+        &self.players[id.0]
+    }
+
+    pub fn player_mut(&mut self, id: PlayerId) -> &mut PlayerState {
+        &mut self.players[id.0]
+    }
+
+    pub fn current(&self) -> &PlayerState {
+        self.player(self.current_player)
+    }
+
+    pub fn current_mut(&mut self) -> &mut PlayerState {
+        self.player_mut(self.current_player)
+    }
+
+    pub fn player_ids(&self) -> impl Iterator<Item = PlayerId> + use<> {
+        (0..self.players.len()).map(PlayerId)
+    }
+
+    // Próximo jogador na ordem da mesa, pulando os eliminados
+    pub fn next_player_after(&self, player: PlayerId) -> PlayerId {
+        let count = self.players.len();
+        (1..=count)
+            .map(|step| PlayerId((player.0 + step) % count))
+            .find(|id| !self.player(*id).eliminated)
+            .unwrap_or(player)
+    }
+
+    pub fn next_player(&self) -> PlayerId {
+        self.next_player_after(self.current_player)
+    }
+
+    pub fn advance_turn(&mut self) {
+        self.current_player = self.next_player();
+    }
+
+    pub fn last_player_standing(&self) -> Option<PlayerId> {
+        let mut remaining = self.player_ids().filter(|id| !self.player(*id).eliminated);
+        match (remaining.next(), remaining.next()) {
+            (Some(winner), None) => Some(winner),
+            _ => None,
+        }
+    }
+
+    // Regra das três faltas valendo na partida
+    pub fn three_foul_rule(&self) -> bool {
+        self.variant.uses_three_foul_rule()
+            && (self.variant != GameVariant::EightBall || self.house_rules.three_foul_loss)
+    }
+
+    // Soma uma falta seguida ao jogador, ou zera a contagem numa tacada válida
+    pub fn track_foul(&mut self, player: PlayerId, fouled: bool) -> u32 {
+        let fouls = &mut self.player_mut(player).consecutive_fouls;
+        *fouls = if fouled { *fouls + 1 } else { 0 };
+        *fouls
+    }
+
+    pub fn clear_pocketed_balls(&mut self) {
+        for player in &mut self.players {
+            player.pocketed_balls.clear();
+        }
+    }
+
+    // Aponta cue_ball_idx para a bola do jogador da vez (na carambola cada um tem a sua)
+    pub fn sync_cue_ball_to_current_player(&mut self) {
+        let number = self.current().cue_ball;
+        self.cue_ball_idx = self.balls.iter().position(|ball| ball.number == number);
+    }
+
+    // Bolas oferecidas no HUD para cantar antes da tacada
+    pub fn callable_balls(&self) -> Vec<u8> {
+        match self.variant {
+            GameVariant::Snooker => self.snooker_callable_balls(),
+            _ => {
+                let mut numbers: Vec<u8> = self.balls.iter()
+                    .filter(|ball| !ball.in_pocket && ball.number != 0)
+                    .map(|ball| ball.number)
+                    .collect();
+                numbers.sort_unstable();
+                numbers
+            }
+        }
+    }
+
+    // Coloca uma bola (encaçapada ou não) parada na posição indicada
+    pub fn place_ball(&mut self, ball_idx: usize, pos: Vec2) {
+        let ball = &mut self.balls[ball_idx];
+        ball.pos = pos;
+        ball.vel = Vec2::ZERO;
+        ball.in_pocket = false;
+    }
+
+    // Posições do triângulo de 15 bolas, começando pelo ápice no foot spot
+    pub fn rack_positions(&self) -> Vec<Vec2> {
+        let r = self.ball_radius;
+        let apex = self.foot_spot();
+        let mut positions = Vec::with_capacity(15);
+        for row in 0..5 {
+            for col in 0..=row {
+                let x = apex.x + row as f32 * (r * 2.0 * 0.8660254);
+                let y = apex.y + col as f32 * r * 2.0 - row as f32 * r;
+                positions.push(vec2(x, y));
+            }
+        }
+        positions
+    }
+
+    // Começo da partida: zera o placar de racks e os relógios e, com lag, decide quem sai
+    fn initialize_game_logic(&mut self) {
+        self.set_state(GameState::Initializing); // Set to initializing first
+        self.notifications.clear();
+        self.ball_definitions_map = Self::definitions_map(self.variant);
+        self.player_count = self.player_count.clamp(*self.variant.player_counts().start(), *self.variant.player_counts().end());
+        self.players = (0..self.player_count).map(|_| PlayerState::new()).collect();
+        self.match_score = vec![0; self.player_count];
+        self.target_score = match self.variant {
+            GameVariant::ThreeCushion => THREE_CUSHION_TARGET_SCORE,
+            _ => STRAIGHT_POOL_TARGET_SCORE,
+        };
+        self.reset_player_clocks();
+
+        if self.match_settings.lag {
+            self.start_lag();
+        } else {
+            self.start_rack(PlayerId(0));
+        }
+    }
+
+    // Arma um novo rack com `breaker` na saída. O tempo total de cada jogador vale
+    // para a partida inteira; o resto do estado do jogador é zerado a cada rack.
+    // Quem já ficou sem tempo total continua fora.
+    pub fn start_rack(&mut self, breaker: PlayerId) {
+        self.set_state(GameState::Initializing);
+        let extensions_left = self.time_control.extensions_per_rack;
+        self.players = self.players.iter()
+            .map(|player| PlayerState {
+                match_time_left: player.match_time_left,
+                extensions_left,
+                eliminated: player.match_time_left.is_some_and(|time_left| time_left <= 0.0),
+                ..PlayerState::new()
+            })
+            .collect();
+        let breaker = if self.player(breaker).eliminated { self.next_player_after(breaker) } else { breaker };
+        self.current_player = breaker;
+        self.breaker = breaker;
+        self.draw_rack_seed();
+        match self.variant {
+            GameVariant::Snooker => self.setup_snooker_balls(),
+            GameVariant::Blackball => self.setup_blackball_balls(),
+            GameVariant::ThreeCushion => self.setup_carom_balls(),
+            _ => self.setup_balls(),
+        }
+        if self.variant == GameVariant::Cutthroat {
+            self.assign_cutthroat_ranges();
+        }
+
+        if self.balls.is_empty() || self.balls[0].number != 0 {
+             self.balls.insert(0,Ball::new(
+                    self.table_width * 0.25,
+                    self.table_height / 2.0,
+                    ball_colors::CUE,0,false,self.ball_radius));
+            self.cue_ball_idx = Some(0);
+        }
+        
+        self.potted_ball_numbers_this_turn.clear();
+        self.shot_log.clear();
+        self.shot_foul = None;
+        self.called_ball = None;
+        self.called_pocket = None;
+        self.ball_in_hand_area = PlacementArea::Kitchen;
+        self.snooker_phase = SnookerPhase::Red;
+        self.free_ball = false;
+        self.extra_visit = false;
+        self.free_shot = false;
+        self.is_break_shot = true;
+        self.reset_shot_clock();
+
+        self.set_state(GameState::Aiming); // Now ready for aiming
+        let racks_played: u32 = self.match_score.iter().sum();
+        self.notify(Notification::RackStarted { rack: racks_played + 1, breaker: self.current_player });
+        self.begin_rack_recording();
+    }
+
+    // Tacada na direção `angle` com força entre 0 e 1. Os comandos daqui para baixo
+    // chegam por Game::execute, que já conferiu o estado; retornam se foram aceitos.
+    pub(crate) fn shoot(&mut self, angle: f32, power: f32) -> bool {
+        // Com a regra de cantar a 8 a tacada na 8 não sai sem caçapa cantada
+        if self.must_call_eight() && self.called_pocket.is_none() {
+            self.notify(Notification::EightPocketRequired { player: self.current_player });
+            return false;
+        }
+        let Some(cb) = self.cue_ball_idx.and_then(|idx| self.balls.get_mut(idx)) else { return false; };
+        if cb.in_pocket { return false; }
+        if power > 0.05 { // Min power threshold
+            let shot_vec = Vec2::from_angle(angle) * power.min(1.0) * self.max_power_shot;
+            cb.vel = shot_vec * BALL_ELASTICITY; // Aplicar elasticidade aqui pode ser melhor
+            self.set_state(GameState::Shooting);
+            self.notify(Notification::BallsMoving);
+            self.potted_ball_numbers_this_turn.clear();
+            self.shot_log.clear();
+            self.shot_foul = None;
+            self.reset_shot_clock();
+            self.record_input(ReplayInput::Shoot { angle, power });
+            true
+        } else {
+            self.notify(Notification::ShotTooWeak { player: self.current_player });
+            false
+        }
+    }
+
+    // Bola na mão: coloca a branca em `pos` se estiver na área permitida e livre.
+    // Retorna true quando a branca foi colocada.
+    pub(crate) fn place_cue_ball(&mut self, pos: Vec2) -> bool {
+        let Some(cb_idx) = self.cue_ball_idx else { return false; };
+        if !self.is_in_cue_ball_area(pos) {
+            self.notify(Notification::CueBallOutsideArea(self.cue_ball_placement_area()));
+            return false;
+        }
+        if !self.is_spot_free(pos, cb_idx) {
+            self.notify(Notification::CueBallOverlaps);
+            return false;
+        }
+        self.place_ball(cb_idx, pos);
+        self.set_state(GameState::Aiming);
+        self.notify(Notification::CueBallPlaced { player: self.current_player });
+        self.record_input(ReplayInput::PlaceCueBall(pos));
+        true
+    }
+
+    pub(crate) fn call_ball(&mut self, number: u8) -> bool {
+        if !self.variant.uses_called_ball() { return false; }
+        self.called_ball = Some(number);
+        self.record_input(ReplayInput::CallBall(number));
+        self.notify(Notification::BallCalled { player: self.current_player, ball: number });
+        true
+    }
+
+    pub(crate) fn call_pocket(&mut self, pocket_idx: usize) -> bool {
+        if !self.must_call_eight() || pocket_idx >= self.pockets.len() { return false; }
+        self.called_pocket = Some(pocket_idx);
+        self.record_input(ReplayInput::CallPocket(pocket_idx));
+        self.notify(Notification::EightPocketCalled { player: self.current_player });
+        true
+    }
+
+    // Avança um quadro: relógio com o tempo decorrido `dt` e um passo da física
+    pub fn update(&mut self, dt: f32) {
+        if self.game_state != GameState::Initializing {
+            self.update_clock(dt);
+            self.update_physics_objects(); // Chama a função de physics.rs
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::Game;
+use crate::ball::Ball;
+use crate::constants::MAX_UNDO_SHOTS;
+use crate::player::PlayerState;
+use crate::replay::ReplayInput;
+use crate::shot::ShotLog;
+use crate::types::{Foul, GameState, PlacementArea, PlayerId, SnookerPhase};
+
+// O que uma tacada muda no jogo: mesa, vez, grupos, pontos e as entradas gravadas do
+// rack. Opções da partida, mesa, script e o começo da gravação não mudam numa tacada
+// e ficam de fora, então cada entrada do histórico é pequena.
+#[derive(Clone)]
+pub struct ShotSnapshot {
+    balls: Vec<Ball>,
+    cue_ball_idx: Option<usize>,
+    game_state: GameState,
+    shot_log: ShotLog,
+    shot_foul: Option<Foul>,
+    called_ball: Option<u8>,
+    called_pocket: Option<usize>,
+    snooker_phase: SnookerPhase,
+    free_ball: bool,
+    extra_visit: bool,
+    free_shot: bool,
+    players: Vec<PlayerState>,
+    current_player: PlayerId,
+    potted_ball_numbers_this_turn: Vec<u8>,
+    is_break_shot: bool,
+    ball_in_hand_area: PlacementArea,
+    shot_time_left: f32,
+    match_score: Vec<u32>,
+    rack_winner: Option<PlayerId>,
+    rack_inputs: Vec<ReplayInput>,
+}
+
+impl ShotSnapshot {
+    pub fn of(game: &Game) -> Self {
+        ShotSnapshot {
+            balls: game.balls.clone(),
+            cue_ball_idx: game.cue_ball_idx,
+            game_state: game.game_state,
+            shot_log: game.shot_log.clone(),
+            shot_foul: game.shot_foul,
+            called_ball: game.called_ball,
+            called_pocket: game.called_pocket,
+            snooker_phase: game.snooker_phase,
+            free_ball: game.free_ball,
+            extra_visit: game.extra_visit,
+            free_shot: game.free_shot,
+            players: game.players.clone(),
+            current_player: game.current_player,
+            potted_ball_numbers_this_turn: game.potted_ball_numbers_this_turn.clone(),
+            is_break_shot: game.is_break_shot,
+            ball_in_hand_area: game.ball_in_hand_area,
+            shot_time_left: game.shot_time_left,
+            match_score: game.match_score.clone(),
+            rack_winner: game.rack_winner,
+            rack_inputs: game.rack_recording.as_ref().map(|recording| recording.inputs.clone()).unwrap_or_default(),
+        }
+    }
+
+    // Põe o retrato no jogo. O estado volta direto, sem set_state: não é uma
+    // transição da partida, é voltar a um ponto em que ela já esteve.
+    fn restore(self, game: &mut Game) {
+        game.balls = self.balls;
+        game.cue_ball_idx = self.cue_ball_idx;
+        game.game_state = self.game_state;
+        game.shot_log = self.shot_log;
+        game.shot_foul = self.shot_foul;
+        game.called_ball = self.called_ball;
+        game.called_pocket = self.called_pocket;
+        game.snooker_phase = self.snooker_phase;
+        game.free_ball = self.free_ball;
+        game.extra_visit = self.extra_visit;
+        game.free_shot = self.free_shot;
+        game.players = self.players;
+        game.current_player = self.current_player;
+        game.potted_ball_numbers_this_turn = self.potted_ball_numbers_this_turn;
+        game.is_break_shot = self.is_break_shot;
+        game.ball_in_hand_area = self.ball_in_hand_area;
+        game.shot_time_left = self.shot_time_left;
+        game.match_score = self.match_score;
+        game.rack_winner = self.rack_winner;
+        if let Some(recording) = game.rack_recording.as_mut() {
+            recording.inputs = self.rack_inputs;
+        }
+        game.notifications.clear();
+    }
+}
+
+// Desfazer e refazer tacadas dentro do rack. Cada entrada é o retrato de antes da
+// tacada, então desfazer volta exatamente ao que era. Um rack novo (ou uma semente
+// nova que refaz o rack) começa o histórico do zero, e as tacadas do lag não entram.
+#[derive(Default)]
+pub struct ShotHistory {
+    undo: Vec<ShotSnapshot>,
+    redo: Vec<ShotSnapshot>,
+}
+
+impl ShotHistory {
+    pub fn new() -> Self {
+        ShotHistory::default()
+    }
+
+    // Guarda o estado de antes de uma tacada; uma tacada nova descarta o que havia para refazer
+    pub fn record(&mut self, before_shot: ShotSnapshot) {
+        if self.undo.len() >= MAX_UNDO_SHOTS {
+            self.undo.remove(0);
+        }
+        self.undo.push(before_shot);
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self, game: &Game) -> bool {
+        !self.undo.is_empty() && !game.balls_in_motion()
+    }
+
+    pub fn can_redo(&self, game: &Game) -> bool {
+        !self.redo.is_empty() && !game.balls_in_motion()
+    }
+
+    // Volta para antes da última tacada; retorna false quando não há o que desfazer
+    pub fn undo(&mut self, game: &mut Game) -> bool {
+        if !self.can_undo(game) { return false; }
+        let Some(before) = self.undo.pop() else { return false; };
+        self.redo.push(ShotSnapshot::of(game));
+        before.restore(game);
+        true
+    }
+
+    pub fn redo(&mut self, game: &mut Game) -> bool {
+        if !self.can_redo(game) { return false; }
+        let Some(after) = self.redo.pop() else { return false; };
+        self.undo.push(ShotSnapshot::of(game));
+        after.restore(game);
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl Game {
+    // Desfazer só quando a partida deixa (MatchSettings::allow_undo) e fora do lag
+    pub fn undo_allowed(&self) -> bool {
+        self.match_settings.allow_undo && !self.is_lagging()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShotHistory, ShotSnapshot};
+    use crate::Game;
+    use crate::replay::Replay;
+    use crate::state_machine::GameCommand;
+    use crate::types::GameState;
+
+    fn shoot(game: &mut Game, history: &mut ShotHistory, angle: f32) {
+        if game.game_state() == GameState::BreakChoice {
+            game.execute(GameCommand::AcceptTable).unwrap();
+        }
+        if game.game_state() == GameState::RepositionCueBall {
+            game.execute(GameCommand::PlaceCueBall(game.head_spot() * 0.9)).unwrap();
+        }
+        let before = ShotSnapshot::of(game);
+        game.execute(GameCommand::Shoot { angle, power: 0.8 }).unwrap();
+        history.record(before);
+        while game.balls_in_motion() {
+            game.update_physics_objects();
+        }
+    }
+
+    fn layout(game: &Game) -> Vec<(u8, glam::Vec2, bool)> {
+        game.balls.iter().map(|ball| (ball.number, ball.pos, ball.in_pocket)).collect()
+    }
+
+    #[test]
+    fn undo_and_redo_go_back_and_forth_with_the_replay_inputs() {
+        let mut game = Game::new();
+        let mut history = ShotHistory::new();
+        let racked = layout(&game);
+        shoot(&mut game, &mut history, 0.0);
+        shoot(&mut game, &mut history, 0.4);
+        let after = layout(&game);
+        let inputs = Replay::of_rack(&game).unwrap().inputs;
+
+        while history.undo(&mut game) {}
+        assert_eq!(layout(&game), racked);
+        assert!(game.is_break_shot);
+        assert!(Replay::of_rack(&game).unwrap().inputs.is_empty());
+
+        while history.redo(&mut game) {}
+        assert_eq!(layout(&game), after);
+        assert_eq!(Replay::of_rack(&game).unwrap().inputs, inputs);
+    }
+}
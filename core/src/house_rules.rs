@@ -0,0 +1,162 @@
+use crate::types::PlacementArea;
+
+// Regras da casa do bola 8, lidas por rules.rs. Cada bar joga um pouco diferente,
+// então as variações ficam aqui em vez de espalhadas pelo código de regras.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HouseRules {
+    // Preset de que as regras saíram; None quando alguma regra foi mudada à parte
+    pub preset: Option<HouseRulesPreset>,
+    // Branca na caçapa junto com a 8 perde o rack (senão a 8 volta para a mesa)
+    pub scratch_on_eight_loses: bool,
+    // 8 encaçapada na saída vence (senão volta para o foot spot)
+    pub eight_on_break_wins: bool,
+    // A mesa continua aberta depois da saída, mesmo com bola encaçapada
+    pub open_table_after_break: bool,
+    // Encaçapar bola do adversário é falta
+    pub opponent_ball_is_foul: bool,
+    // Onde a branca pode ser colocada depois de uma falta (na saída é sempre a área de saída)
+    pub ball_in_hand: PlacementArea,
+    // A caçapa da 8 precisa ser cantada
+    pub call_the_eight: bool,
+    // Com lisas e listradas encaçapadas na saída quem saiu escolhe o grupo
+    pub choose_group_after_mixed_break: bool,
+    // A terceira falta seguida perde o rack
+    pub three_foul_loss: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HouseRulesPreset {
+    // O comportamento original do jogo, que não conta faltas seguidas
+    #[default]
+    Classic,
+    Official,
+    Bar,
+}
+
+// Cada regra que dá para ligar e desligar sozinha, na ordem da tela de regras
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HouseRule {
+    ScratchOnEightLoses,
+    EightOnBreakWins,
+    OpenTableAfterBreak,
+    OpponentBallIsFoul,
+    BallInHandAnywhere,
+    CallTheEight,
+    ChooseGroupAfterMixedBreak,
+    ThreeFoulLoss,
+}
+
+impl HouseRulesPreset {
+    pub const ALL: [HouseRulesPreset; 3] = [HouseRulesPreset::Classic, HouseRulesPreset::Official, HouseRulesPreset::Bar];
+
+    pub fn rules(self) -> HouseRules {
+        match self {
+            HouseRulesPreset::Classic => HouseRules::CLASSIC,
+            HouseRulesPreset::Official => HouseRules::OFFICIAL,
+            HouseRulesPreset::Bar => HouseRules::BAR,
+        }
+    }
+}
+
+impl HouseRule {
+    pub const ALL: [HouseRule; 8] = [
+        HouseRule::ScratchOnEightLoses,
+        HouseRule::EightOnBreakWins,
+        HouseRule::OpenTableAfterBreak,
+        HouseRule::OpponentBallIsFoul,
+        HouseRule::BallInHandAnywhere,
+        HouseRule::CallTheEight,
+        HouseRule::ChooseGroupAfterMixedBreak,
+        HouseRule::ThreeFoulLoss,
+    ];
+}
+
+impl HouseRules {
+    pub const CLASSIC: HouseRules = HouseRules {
+        preset: Some(HouseRulesPreset::Classic),
+        scratch_on_eight_loses: true,
+        eight_on_break_wins: false,
+        open_table_after_break: false,
+        opponent_ball_is_foul: false,
+        ball_in_hand: PlacementArea::Kitchen,
+        call_the_eight: false,
+        choose_group_after_mixed_break: false,
+        three_foul_loss: false,
+    };
+
+    pub const OFFICIAL: HouseRules = HouseRules {
+        preset: Some(HouseRulesPreset::Official),
+        scratch_on_eight_loses: true,
+        eight_on_break_wins: false,
+        open_table_after_break: true,
+        opponent_ball_is_foul: false,
+        ball_in_hand: PlacementArea::Anywhere,
+        call_the_eight: true,
+        choose_group_after_mixed_break: false,
+        three_foul_loss: false,
+    };
+
+    pub const BAR: HouseRules = HouseRules {
+        preset: Some(HouseRulesPreset::Bar),
+        scratch_on_eight_loses: false,
+        eight_on_break_wins: true,
+        open_table_after_break: false,
+        opponent_ball_is_foul: true,
+        ball_in_hand: PlacementArea::Kitchen,
+        call_the_eight: true,
+        choose_group_after_mixed_break: true,
+        three_foul_loss: true,
+    };
+
+    // Próximo preset; regras mudadas à parte voltam ao primeiro
+    pub fn next(&self) -> HouseRules {
+        let idx = self.preset
+            .and_then(|preset| HouseRulesPreset::ALL.iter().position(|&other| other == preset))
+            .map_or(0, |idx| (idx + 1) % HouseRulesPreset::ALL.len());
+        HouseRulesPreset::ALL[idx].rules()
+    }
+
+    pub fn is_on(&self, rule: HouseRule) -> bool {
+        match rule {
+            HouseRule::ScratchOnEightLoses => self.scratch_on_eight_loses,
+            HouseRule::EightOnBreakWins => self.eight_on_break_wins,
+            HouseRule::OpenTableAfterBreak => self.open_table_after_break,
+            HouseRule::OpponentBallIsFoul => self.opponent_ball_is_foul,
+            HouseRule::BallInHandAnywhere => self.ball_in_hand == PlacementArea::Anywhere,
+            HouseRule::CallTheEight => self.call_the_eight,
+            HouseRule::ChooseGroupAfterMixedBreak => self.choose_group_after_mixed_break,
+            HouseRule::ThreeFoulLoss => self.three_foul_loss,
+        }
+    }
+
+    pub fn set(&mut self, rule: HouseRule, on: bool) {
+        match rule {
+            HouseRule::ScratchOnEightLoses => self.scratch_on_eight_loses = on,
+            HouseRule::EightOnBreakWins => self.eight_on_break_wins = on,
+            HouseRule::OpenTableAfterBreak => self.open_table_after_break = on,
+            HouseRule::OpponentBallIsFoul => self.opponent_ball_is_foul = on,
+            HouseRule::BallInHandAnywhere => {
+                self.ball_in_hand = if on { PlacementArea::Anywhere } else { PlacementArea::Kitchen };
+            }
+            HouseRule::CallTheEight => self.call_the_eight = on,
+            HouseRule::ChooseGroupAfterMixedBreak => self.choose_group_after_mixed_break = on,
+            HouseRule::ThreeFoulLoss => self.three_foul_loss = on,
+        }
+        // Voltando a bater com um preset, as regras passam a ser dele de novo
+        self.preset = HouseRulesPreset::ALL.into_iter()
+            .find(|&preset| preset.rules() == HouseRules { preset: Some(preset), ..*self });
+    }
+
+    pub fn toggle(&mut self, rule: HouseRule) {
+        self.set(rule, !self.is_on(rule));
+    }
+}
+
+impl Default for HouseRules {
+    fn default() -> Self {
+        HouseRules::CLASSIC
+    }
+}
@@ -0,0 +1,36 @@
+// Simulação, regras e estado da partida, sem janela nem dependência do macroquad.
+// O front end (o binário potshot) desenha, traduz o input em chamadas a Game e
+// escreve os textos: daqui só saem enums e erros, nunca frase pronta.
+
+pub mod types;
+pub mod constants;
+pub mod colors;
+pub mod config;
+pub mod ball;
+pub mod pocket;
+pub mod table;
+pub mod physics;
+pub mod rules;
+pub mod shot;
+pub mod blackball;
+pub mod carom;
+pub mod snooker;
+pub mod straight_pool;
+pub mod cutthroat;
+pub mod spotting;
+pub mod clock;
+pub mod match_play;
+pub mod house_rules;
+pub mod player;
+pub mod game;
+pub mod notification;
+pub mod state_machine;
+pub mod replay;
+pub mod history;
+pub mod rack;
+pub mod script;
+#[cfg(feature = "serde")]
+pub mod save;
+
+pub use game::Game;
+pub use glam::{Vec2, vec2};
@@ -0,0 +1,162 @@
+use crate::Game;
+use crate::ball::Ball;
+use crate::colors::ball_colors;
+use crate::shot::ShotEvent;
+use crate::notification::Notification;
+use crate::types::{GameState, PlayerId};
+
+// Quem sai no rack seguinte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BreakRotation {
+    WinnerBreaks,
+    Alternate,
+    LoserBreaks,
+}
+
+// Partida em corrida: vence quem ganhar `race_to` racks primeiro
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MatchSettings {
+    pub race_to: u32,
+    pub rotation: BreakRotation,
+    pub lag: bool,
+    // Desfazer e refazer tacadas; a partida competitiva desliga
+    pub allow_undo: bool,
+}
+
+impl MatchSettings {
+    pub const SINGLE_RACK: MatchSettings =
+        MatchSettings { race_to: 1, rotation: BreakRotation::WinnerBreaks, lag: false, allow_undo: true };
+
+    // Opções oferecidas no botão da partida, em ordem
+    pub const PRESETS: [MatchSettings; 5] = [
+        MatchSettings::SINGLE_RACK,
+        MatchSettings { race_to: 3, rotation: BreakRotation::Alternate, lag: true, allow_undo: false },
+        MatchSettings { race_to: 5, rotation: BreakRotation::WinnerBreaks, lag: true, allow_undo: false },
+        MatchSettings { race_to: 7, rotation: BreakRotation::LoserBreaks, lag: true, allow_undo: false },
+        MatchSettings { race_to: 9, rotation: BreakRotation::Alternate, lag: true, allow_undo: false },
+    ];
+
+    // Próximo preset, que traz o desfazer dele; o botão do desfazer muda só esse campo
+    pub fn next(&self) -> MatchSettings {
+        let idx = Self::PRESETS.iter()
+            .position(|preset| MatchSettings { allow_undo: preset.allow_undo, ..*self } == *preset)
+            .unwrap_or(0);
+        Self::PRESETS[(idx + 1) % Self::PRESETS.len()]
+    }
+
+    pub fn is_single_rack(&self) -> bool {
+        self.race_to <= 1
+    }
+}
+
+impl Default for MatchSettings {
+    fn default() -> Self {
+        MatchSettings::SINGLE_RACK
+    }
+}
+
+impl Game {
+    // Fecha o rack com o vencedor. Chegando à meta da corrida a partida termina;
+    // senão o jogo espera o botão de próximo rack.
+    pub fn end_rack(&mut self, winner: PlayerId) {
+        self.rack_winner = Some(winner);
+        self.match_score[winner.0] += 1;
+        self.set_state(if self.match_score[winner.0] >= self.match_settings.race_to {
+            GameState::MatchOver
+        } else {
+            GameState::GameOver
+        });
+    }
+
+    // Fecha a partida com o vencedor sem contar o rack, como quando o tempo do
+    // adversário acaba
+    pub fn end_match(&mut self, winner: PlayerId) {
+        self.rack_winner = Some(winner);
+        self.set_state(GameState::MatchOver);
+    }
+
+    pub(crate) fn start_next_rack(&mut self) {
+        let breaker = self.next_breaker();
+        self.rack_winner = None;
+        self.start_rack(breaker);
+    }
+
+    fn next_breaker(&self) -> PlayerId {
+        let winner = self.rack_winner.unwrap_or(self.breaker);
+        match self.match_settings.rotation {
+            BreakRotation::WinnerBreaks => winner,
+            BreakRotation::Alternate => PlayerId((self.breaker.0 + 1) % self.players.len()),
+            // Com mais de dois jogadores sai quem vem depois do vencedor
+            BreakRotation::LoserBreaks => PlayerId((winner.0 + 1) % self.players.len()),
+        }
+    }
+
+    // Quem fechou a corrida ou ficou sozinho quando o tempo dos outros acabou
+    pub fn match_winner(&self) -> Option<PlayerId> {
+        self.rack_winner.filter(|_| self.game_state == GameState::MatchOver)
+    }
+
+    pub fn match_score_text(&self) -> String {
+        self.match_score.iter().map(|score| score.to_string()).collect::<Vec<_>>().join("-")
+    }
+
+    // Lag: cada jogador manda a branca do head spot até a tabela de fundo e de volta.
+    // Quem parar mais perto da tabela de cabeceira escolhe sair.
+    pub fn start_lag(&mut self) {
+        self.lag_results = Some(vec![None; self.players.len()]);
+        self.current_player = PlayerId(0);
+        self.balls.clear();
+        let head_spot = self.head_spot();
+        self.balls.push(Ball::new(head_spot.x, head_spot.y, ball_colors::CUE, 0, false, self.ball_radius));
+        self.cue_ball_idx = Some(0);
+        self.shot_log.clear();
+        self.potted_ball_numbers_this_turn.clear();
+        self.reset_shot_clock();
+        // O lag não entra no replay, que começa na saída
+        self.rack_recording = None;
+        self.set_state(GameState::Aiming);
+        self.notify(Notification::LagStarted { player: self.current_player });
+    }
+
+    pub fn is_lagging(&self) -> bool {
+        self.lag_results.is_some()
+    }
+
+    pub fn handle_lag_shot_end(&mut self) {
+        let Some(mut results) = self.lag_results.take() else { return; };
+        let ball = &self.balls[0];
+        // Só vale o lag que bate em tabela e não cai na caçapa
+        let touched_cushion = self.shot_log.events.iter().any(|event| matches!(event, ShotEvent::Cushion(0)));
+        results[self.current_player.0] = (touched_cushion && !ball.in_pocket).then_some(ball.pos.x);
+
+        let next = self.current_player.0 + 1;
+        if next < results.len() {
+            self.lag_results = Some(results);
+            self.current_player = PlayerId(next);
+            let head_spot = self.head_spot();
+            self.place_ball(0, head_spot);
+            self.reset_shot_clock();
+            self.set_state(GameState::Aiming);
+            self.notify(Notification::LagTurn { player: self.current_player });
+            return;
+        }
+
+        let winner = results.iter().enumerate()
+            .filter_map(|(i, distance)| distance.map(|d| (i, d)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| PlayerId(i));
+        match winner {
+            Some(winner) => {
+                self.notify(Notification::LagWon { player: winner });
+                self.start_rack(winner);
+            }
+            None => {
+                self.notify(Notification::LagRepeated);
+                self.start_lag();
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::Game;
+use crate::types::{Foul, GameState, PlacementArea, PlayerGroup, PlayerId};
+use crate::script::ScriptError;
+
+// Avisos da partida para quem mostra o jogo. As regras só enfileiram o que
+// aconteceu; o texto, o idioma e quantos avisos mostrar ficam com a interface.
+// Uma tacada costuma gerar vários, na ordem em que as coisas aconteceram.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Notification {
+    // Rack `rack` (contando do 1) começa com `breaker` na saída
+    RackStarted { rack: u32, breaker: PlayerId },
+    BallsMoving,
+    ShotTooWeak { player: PlayerId },
+    CueBallOutsideArea(PlacementArea),
+    CueBallOverlaps,
+    CueBallPlaced { player: PlayerId },
+    BallCalled { player: PlayerId, ball: u8 },
+    EightPocketCalled { player: PlayerId },
+    EightPocketRequired { player: PlayerId },
+
+    // Como a vez ficou depois da tacada
+    TurnChanged { player: PlayerId },
+    PlaysAgain { player: PlayerId },
+    BallInHand { player: PlayerId, area: PlacementArea },
+
+    // O que decidiu a tacada
+    Foul { player: PlayerId, reason: Foul },
+    FoulWarning { player: PlayerId },
+    ThirdFoul { player: PlayerId },
+    GoodShot,
+    NothingPotted,
+    WrongBallPotted,
+    NoOwnBall,
+    OpenTable { after_break: bool },
+    GroupAssigned { player: PlayerId, group: PlayerGroup },
+    GroupChoice { player: PlayerId },
+    OnTheEight { player: PlayerId },
+    EightRespotted,
+    EightOnBreak,
+    IllegalBreak { rail_balls: usize, chooser: PlayerId },
+    Reracked { breaker: PlayerId },
+    TableAccepted { player: PlayerId },
+
+    // Pontos
+    Scored { player: PlayerId, points: i32 },
+    PointsLost { player: PlayerId, points: i32 },
+    PointsAwarded { player: PlayerId, points: i32 },
+    NoPoints,
+    LegalBreak,
+    NewRack,
+    CaromPoint { player: PlayerId, score: i32 },
+
+    // Variantes
+    BlackOnBreak { breaker: PlayerId },
+    TwoShots { player: PlayerId },
+    SecondVisit { player: PlayerId },
+    FreeBall { player: PlayerId },
+    SnookerTie,
+
+    // Lag e relógio
+    LagStarted { player: PlayerId },
+    LagTurn { player: PlayerId },
+    LagWon { player: PlayerId },
+    LagRepeated,
+    ExtensionUsed { player: PlayerId, seconds: f32 },
+    ShotClockExpired { player: PlayerId },
+
+    // Fim do rack ou de um jogador
+    Eliminated { player: PlayerId, reason: LossReason },
+    Lost { player: PlayerId, reason: LossReason },
+    Won { player: PlayerId },
+    FrameWon { player: PlayerId, high: i32, low: i32 },
+
+    // Erro no script da variante; a jogada segue sem as decisões dele
+    ScriptError(ScriptError),
+    // Erro de regra: set_state recusou a transição e o estado ficou como estava.
+    // Vai para o log, não para o jogador.
+    InvalidTransition { from: GameState, to: GameState },
+}
+
+// Por que um jogador perdeu o rack ou saiu dele
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LossReason {
+    ThirdFoul,
+    OutOfTime,
+    NoBallsLeft,
+    EightAndScratch,
+    EightOnOpenTable,
+    EightTooEarly,
+    EightWrongPocket,
+    BlackIllegal,
+    // Decidido pelo script da variante
+    Rules,
+}
+
+impl Game {
+    pub(crate) fn notify(&mut self, notification: Notification) {
+        self.notifications.push(notification);
+    }
+
+    // Avisos desde a última chamada, em ordem
+    pub fn take_notifications(&mut self) -> Vec<Notification> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    // Bola na mão para o jogador da vez, na área da variante
+    pub(crate) fn notify_ball_in_hand(&mut self) {
+        self.notify(Notification::BallInHand { player: self.current_player, area: self.cue_ball_placement_area() });
+    }
+}
@@ -0,0 +1,145 @@
+use glam::{Vec2, vec2};
+use crate::ball::Ball;
+use crate::constants::{MIN_SPEED, FRICTION, CUSHION_ELASTICITY, BALL_ELASTICITY};
+use crate::Game; // Para acessar self.balls, self.pockets
+use crate::shot::ShotEvent;
+use crate::types::GameState;
+
+// Perfis de física escolhidos nas configurações. Valem para a partida inteira
+// (ficam no Game), então o replay de um rack reproduz com a física em que foi jogado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhysicsProfile {
+    #[default]
+    Standard,
+    // Pano rápido: as bolas rolam mais e as tabelas devolvem mais
+    Fast,
+    // Pano pesado: as bolas param antes
+    Heavy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicsParams {
+    pub friction: f32,
+    pub cushion_elasticity: f32,
+    pub ball_elasticity: f32,
+}
+
+impl PhysicsProfile {
+    pub const ALL: [PhysicsProfile; 3] = [PhysicsProfile::Standard, PhysicsProfile::Fast, PhysicsProfile::Heavy];
+
+    pub fn next(self) -> PhysicsProfile {
+        let idx = Self::ALL.iter().position(|&profile| profile == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn params(self) -> PhysicsParams {
+        match self {
+            PhysicsProfile::Standard => PhysicsParams {
+                friction: FRICTION,
+                cushion_elasticity: CUSHION_ELASTICITY,
+                ball_elasticity: BALL_ELASTICITY,
+            },
+            PhysicsProfile::Fast => PhysicsParams { friction: 0.985, cushion_elasticity: 0.85, ball_elasticity: BALL_ELASTICITY },
+            PhysicsProfile::Heavy => PhysicsParams { friction: 0.97, cushion_elasticity: 0.75, ball_elasticity: 0.93 },
+        }
+    }
+}
+
+impl Game { // Adicionando métodos de física à struct Game
+    pub fn update_physics_objects(&mut self) { // Renomeado para evitar conflito com update em game.rs
+        if self.balls_in_motion() {
+            let params = self.physics.params();
+            let cushion_elasticity = params.cushion_elasticity * self.table.cushion.rebound;
+            let mut still_moving = false;
+            for i in 0..self.balls.len() {
+                if self.balls[i].in_pocket { continue; }
+
+                self.balls[i].update_position(params.friction); // Ball 자체의 update_position 호출
+                if self.balls[i].check_wall_collision(self.table_width, self.table_height, cushion_elasticity, &self.pockets) {
+                    self.shot_log.record(ShotEvent::Cushion(self.balls[i].number));
+                }
+                self.check_pocket_collision_for_ball(i);
+
+                if self.balls[i].vel.length_squared() > MIN_SPEED.powi(2) {
+                    still_moving = true;
+                }
+            }
+
+            let num_balls = self.balls.len();
+            for i in 0..num_balls {
+                if self.balls[i].in_pocket { continue; }
+                let (first_half, second_half) = self.balls.split_at_mut(i + 1);
+                let ball1 = &mut first_half[i];
+                for ball2 in second_half.iter_mut() {
+                    if ball2.in_pocket { continue; }
+                    if Self::handle_ball_to_ball_collision(ball1, ball2, params.ball_elasticity) {
+                        self.shot_log.record(ShotEvent::BallContact(ball1.number, ball2.number));
+                    }
+                }
+            }
+
+            // Toda tacada passa por BallsMoving, mesmo a que para no primeiro quadro
+            if self.game_state == GameState::Shooting {
+                self.set_state(GameState::BallsMoving);
+            } else if !still_moving {
+                self.spot_balls_off_table();
+                self.handle_player_turn_end(); // Esta função está em rules.rs agora, chamada por game.rs
+            }
+        }
+    }
+
+    pub fn check_pocket_collision_for_ball(&mut self, ball_idx: usize) {
+        if self.balls[ball_idx].in_pocket { return; }
+
+        for (pocket_idx, pocket) in self.pockets.iter().enumerate() {
+            if pocket.captures(self.balls[ball_idx].pos) {
+                if !self.balls[ball_idx].in_pocket {
+                    self.balls[ball_idx].in_pocket = true;
+                    self.balls[ball_idx].vel = Vec2::ZERO;
+                    
+                    let ball_number = self.balls[ball_idx].number;
+                    self.shot_log.record(ShotEvent::Pocketed(ball_number, pocket_idx));
+                    if ball_number != 0 {
+                        self.potted_ball_numbers_this_turn.push(ball_number);
+                        
+                        if let Some(def) = self.ball_definitions_map.get(&ball_number).cloned() {
+                            self.current_mut().pocketed_balls.push(def);
+                        }
+                    }
+                }
+                break; 
+            }
+        }
+    }
+    
+    // Retorna true quando as bolas se chocam de fato (não apenas encostadas e paradas)
+    fn handle_ball_to_ball_collision(b1: &mut Ball, b2: &mut Ball, elasticity: f32) -> bool {
+        if b1.in_pocket || b2.in_pocket { return false; }
+        let delta = b2.pos - b1.pos;
+        let dist_sq = delta.length_squared();
+        let sum_radii = b1.radius + b2.radius;
+
+        if dist_sq < sum_radii * sum_radii && dist_sq > 0.001 {
+            let dist = dist_sq.sqrt();
+            let overlap = (sum_radii - dist) / 2.0;
+            let correction_vec = delta.normalize_or_zero() * overlap;
+            b1.pos -= correction_vec;
+            b2.pos += correction_vec;
+
+            let normal = delta.normalize_or_zero();
+            let approaching = (b1.vel - b2.vel).dot(normal) > 0.0;
+            let tangent = vec2(-normal.y, normal.x);
+            let dp_tan1 = b1.vel.dot(tangent);
+            let dp_tan2 = b2.vel.dot(tangent);
+            let dp_norm1 = b1.vel.dot(normal);
+            let dp_norm2 = b2.vel.dot(normal);
+
+            b1.vel = tangent * dp_tan1 + normal * dp_norm2 * elasticity;
+            b2.vel = tangent * dp_tan2 + normal * dp_norm1 * elasticity;
+            return approaching;
+        }
+        false
+    }
+}
+
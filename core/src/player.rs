@@ -0,0 +1,37 @@
+use crate::config::BallDefinition;
+use crate::types::PlayerGroup;
+
+// Estado de cada jogador na partida, guardado em Game::players
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerState {
+    pub group: PlayerGroup,
+    pub pocketed_balls: Vec<BallDefinition>,
+    pub score: i32,
+    pub consecutive_fouls: u32,
+    pub cue_ball: u8,
+    pub eliminated: bool,
+    pub extensions_left: u32,
+    pub match_time_left: Option<f32>,
+}
+
+impl PlayerState {
+    pub fn new() -> Self {
+        PlayerState {
+            group: PlayerGroup::Undecided,
+            pocketed_balls: Vec::new(),
+            score: 0,
+            consecutive_fouls: 0,
+            cue_ball: 0,
+            eliminated: false,
+            extensions_left: 0,
+            match_time_left: None,
+        }
+    }
+}
+
+impl Default for PlayerState {
+    fn default() -> Self {
+        PlayerState::new()
+    }
+}
@@ -0,0 +1,44 @@
+use glam::Vec2;
+
+// Caçapa em unidades da mesa. A bola não bate na tabela enquanto passa pela boca
+// e cai quando o centro dela passa da boca mais que o shelf.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pocket {
+    // Buraco desenhado e tocado para cantar a caçapa
+    pub pos: Vec2,
+    pub radius: f32,
+    // Meio da boca e a direção para fora da mesa
+    pub mouth: Vec2,
+    pub normal: Vec2,
+    pub shelf: f32,
+}
+
+impl Pocket {
+    // `edge` é o ponto na linha das tabelas: o canto, nas caçapas de canto, onde a
+    // boca atravessa o canto na diagonal
+    pub fn new(edge: Vec2, normal: Vec2, mouth_width: f32, shelf: f32) -> Self {
+        let radius = mouth_width / 2.0;
+        let mouth = if Self::is_corner_normal(normal) { edge - normal * radius } else { edge };
+        Pocket { pos: mouth + normal * shelf, radius, mouth, normal, shelf }
+    }
+
+    fn is_corner_normal(normal: Vec2) -> bool {
+        normal.x != 0.0 && normal.y != 0.0
+    }
+
+    fn edge(&self) -> Vec2 {
+        if Self::is_corner_normal(self.normal) { self.mouth + self.normal * self.radius } else { self.mouth }
+    }
+
+    // A tabela de normal `rail` está aberta em `pos`: a bola passa em vez de bater
+    pub fn opens(&self, rail: Vec2, pos: Vec2) -> bool {
+        let facing = self.normal.dot(rail);
+        facing > 0.1 && (pos - self.edge()).dot(rail.perp()).abs() <= self.radius / facing
+    }
+
+    pub fn captures(&self, pos: Vec2) -> bool {
+        let offset = pos - self.mouth;
+        offset.dot(self.normal) >= self.shelf && offset.dot(self.normal.perp()).abs() <= self.radius
+    }
+}
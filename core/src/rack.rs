@@ -0,0 +1,242 @@
+use glam::{Vec2, vec2};
+
+use crate::Game;
+use crate::ball::Ball;
+use crate::colors::ball_colors;
+use crate::config::ball_definitions_for;
+use crate::notification::Notification;
+use crate::script::ScriptError;
+use crate::types::{GameState, GameVariant};
+
+// Folga entre as bolas do triângulo. Um rack justo sai sempre igual; com folga
+// cada bola ganha um deslocamento pequeno e a saída varia como num rack de verdade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RackLooseness {
+    #[default]
+    Tight,
+    Slight,
+    Loose,
+}
+
+impl RackLooseness {
+    pub fn next(self) -> RackLooseness {
+        match self {
+            RackLooseness::Tight => RackLooseness::Slight,
+            RackLooseness::Slight => RackLooseness::Loose,
+            RackLooseness::Loose => RackLooseness::Tight,
+        }
+    }
+
+    // Folga máxima em frações do raio da bola
+    fn gap_fraction(self) -> f32 {
+        match self {
+            RackLooseness::Tight => 0.0,
+            RackLooseness::Slight => 0.02,
+            RackLooseness::Loose => 0.06,
+        }
+    }
+}
+
+// Como armar os racks: com `seed` todo rack sai da mesma semente (para repetir um
+// rack); sem ela cada rack tira uma semente nova
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RackOptions {
+    pub seed: Option<u32>,
+    pub looseness: RackLooseness,
+}
+
+// Gerador pequeno e determinístico (SplitMix64): a mesma semente arma o mesmo rack
+// em qualquer plataforma
+#[derive(Debug, Clone)]
+pub struct RackRng(u64);
+
+impl RackRng {
+    pub fn new(seed: u64) -> Self {
+        RackRng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Inteiro em 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // Real em [0, 1)
+    pub fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// Posições do triângulo com regra: ápice, centro (3ª fileira) e cantos de trás
+const APEX: usize = 0;
+const CENTER: usize = 4;
+const BACK_CORNERS: [usize; 2] = [10, 14];
+
+impl Game {
+    // Semente do próximo rack: a fixa, se houver, ou a próxima da sequência
+    pub(crate) fn draw_rack_seed(&mut self) {
+        self.rack_seed = match self.rack_options.seed {
+            Some(seed) => seed,
+            None => RackRng::new(self.rack_seed_state).next_u64() as u32,
+        };
+        self.rack_seed_state = self.rack_seed_state.wrapping_add(1);
+    }
+
+    // Troca a semente fixa. Antes da saída o rack atual é refeito com a semente nova.
+    pub fn set_rack_seed(&mut self, seed: Option<u32>) {
+        self.rack_options.seed = seed;
+        if let Some(seed) = seed
+            && seed != self.rack_seed
+            && self.is_break_shot
+            && self.game_state == GameState::Aiming
+            && !self.is_lagging()
+        {
+            self.start_rack(self.breaker);
+        }
+    }
+
+    // Ordem das 15 bolas no triângulo, sorteada pelas regras da modalidade
+    fn rack_order(&self, rng: &mut RackRng) -> [u8; 15] {
+        let mut order: [u8; 15] = std::array::from_fn(|i| i as u8 + 1);
+        rng.shuffle(&mut order);
+        let mut put = |slot: usize, number: u8| {
+            let idx = order.iter().position(|&n| n == number).unwrap_or(slot);
+            order.swap(slot, idx);
+        };
+        match self.variant {
+            // Bola 8 no centro e uma lisa e uma listrada nos cantos de trás
+            GameVariant::EightBall => {
+                put(CENTER, 8);
+                let solid = [1, 2, 3, 4, 5, 6, 7][rng.below(7)];
+                let stripe = [9, 10, 11, 12, 13, 14, 15][rng.below(7)];
+                let (left, right) = if rng.below(2) == 0 { (solid, stripe) } else { (stripe, solid) };
+                put(BACK_CORNERS[0], left);
+                put(BACK_CORNERS[1], right);
+            }
+            // Bola 1 no ápice e a primeira de cada uma das outras faixas nos cantos
+            GameVariant::Cutthroat => {
+                put(APEX, 1);
+                let (left, right) = if rng.below(2) == 0 { (6, 11) } else { (11, 6) };
+                put(BACK_CORNERS[0], left);
+                put(BACK_CORNERS[1], right);
+            }
+            _ => {}
+        }
+        order
+    }
+
+    // Triângulo com folga: as bolas ficam `gap` mais afastadas e cada uma se desloca
+    // até `gap` numa direção qualquer, então nunca se sobrepõem
+    fn loose_rack_positions(&self, rng: &mut RackRng) -> Vec<Vec2> {
+        let gap = self.ball_radius * self.rack_options.looseness.gap_fraction();
+        let d = self.ball_radius * 2.0 + gap * 2.0;
+        let apex = self.foot_spot();
+        let mut positions = Vec::with_capacity(15);
+        for row in 0..5 {
+            for col in 0..=row {
+                let pos = apex + vec2(row as f32 * d * 0.8660254, col as f32 * d - row as f32 * d * 0.5);
+                let jitter = Vec2::from_angle(rng.unit() * std::f32::consts::TAU) * gap * rng.unit();
+                positions.push(pos + jitter);
+            }
+        }
+        positions
+    }
+
+    pub fn setup_balls(&mut self) {
+        self.balls.clear();
+        let r = self.ball_radius;
+
+        self.balls.push(Ball::new(
+            self.table_width * 0.25,
+            self.table_height / 2.0,
+            ball_colors::CUE, 0, false, r,
+        ));
+        self.cue_ball_idx = Some(0);
+
+        let mut rng = RackRng::new(self.rack_seed as u64);
+        let ball_defs = ball_definitions_for(self.variant);
+        let order = match self.variant {
+            GameVariant::Scripted => self.scripted_rack_order(&mut rng),
+            _ => None,
+        }.unwrap_or_else(|| self.rack_order(&mut rng).to_vec());
+        for (pos, number) in self.loose_rack_positions(&mut rng).into_iter().zip(order) {
+            // Só o script escolhe números; um que a modalidade não tem fica fora do rack
+            let Some(def) = number.checked_sub(1).and_then(|idx| ball_defs.get(idx as usize)) else {
+                self.notify(Notification::ScriptError(ScriptError::Rack(number.to_string())));
+                continue;
+            };
+            self.balls.push(Ball::new(pos.x, pos.y, def.color, def.number, def.is_striped, r));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+    use super::{RackLooseness, RackOptions};
+    use crate::Game;
+    use crate::types::{GameState, GameVariant};
+
+    fn racked(variant: GameVariant, seed: u32, looseness: RackLooseness) -> Game {
+        let mut game = Game::new();
+        game.variant = variant;
+        game.rack_options = RackOptions { seed: Some(seed), looseness };
+        game.new_match();
+        game
+    }
+
+    fn layout(game: &Game) -> Vec<(u8, Vec2)> {
+        game.balls.iter().map(|ball| (ball.number, ball.pos)).collect()
+    }
+
+    #[test]
+    fn same_seed_racks_the_same_balls() {
+        for variant in [GameVariant::EightBall, GameVariant::Blackball, GameVariant::Cutthroat, GameVariant::StraightPool] {
+            let first = racked(variant, 1234, RackLooseness::Loose);
+            let second = racked(variant, 1234, RackLooseness::Loose);
+            assert_eq!(first.rack_seed, 1234);
+            assert_eq!(layout(&first), layout(&second), "{:?}", variant);
+        }
+    }
+
+    #[test]
+    fn other_seed_racks_other_balls() {
+        let first = racked(GameVariant::EightBall, 1, RackLooseness::Loose);
+        let second = racked(GameVariant::EightBall, 2, RackLooseness::Loose);
+        assert_ne!(layout(&first), layout(&second));
+    }
+
+    #[test]
+    fn drawn_seeds_follow_the_seed_state() {
+        let draw = || {
+            let mut game = Game::new();
+            game.rack_seed_state = 42;
+            game.new_match();
+            (game.rack_seed, layout(&game))
+        };
+        assert_eq!(draw(), draw());
+    }
+
+    #[test]
+    fn new_seed_before_the_break_reracks() {
+        let mut game = racked(GameVariant::EightBall, 1, RackLooseness::Slight);
+        game.set_rack_seed(Some(2));
+        assert_eq!(game.game_state(), GameState::Aiming);
+        assert_eq!(layout(&game), layout(&racked(GameVariant::EightBall, 2, RackLooseness::Slight)));
+    }
+}
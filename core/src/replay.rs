@@ -0,0 +1,323 @@
+use glam::Vec2;
+
+use crate::Game;
+use crate::types::{GameState, GameVariant, PlayerGroup, PlayerId};
+use crate::state_machine::GameCommand;
+use crate::table::TableSpec;
+use crate::house_rules::HouseRules;
+use crate::clock::TimeControl;
+use crate::match_play::MatchSettings;
+use crate::rack::RackOptions;
+use crate::physics::PhysicsProfile;
+use crate::script::ScriptedVariant;
+
+// Tudo que muda o rack fora da física, na ordem em que aconteceu. Com o jogo da
+// saída isso basta para rejogar o rack inteiro: a física é por quadro e
+// determinística.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReplayInput {
+    PlaceCueBall(Vec2),
+    CallBall(u8),
+    CallPocket(usize),
+    Shoot { angle: f32, power: f32 },
+    Rerack,
+    AcceptTable,
+    ChooseGroup(PlayerGroup),
+    UseExtension,
+    ShotClockExpired,
+    MatchTimeExpired,
+}
+
+// Replay de um rack: as opções da partida, o que passa de um rack para o outro
+// (placar, tempo total, quem sai), a semente do rack e as entradas. O jogo da saída
+// é armado de novo a partir disso em start_game.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    pub variant: GameVariant,
+    pub table: TableSpec,
+    pub player_count: usize,
+    pub house_rules: HouseRules,
+    pub time_control: TimeControl,
+    pub match_settings: MatchSettings,
+    pub rack_options: RackOptions,
+    pub physics: PhysicsProfile,
+    pub script: Option<ScriptedVariant>,
+    pub match_score: Vec<u32>,
+    pub match_time_left: Vec<Option<f32>>,
+    pub breaker: PlayerId,
+    pub rack_seed: u32,
+    pub inputs: Vec<ReplayInput>,
+}
+
+impl Replay {
+    // Começo da gravação, com o rack recém-armado
+    fn starting(game: &Game) -> Replay {
+        Replay {
+            variant: game.variant,
+            table: game.table.clone(),
+            player_count: game.player_count,
+            house_rules: game.house_rules,
+            time_control: game.time_control,
+            match_settings: game.match_settings,
+            rack_options: game.rack_options,
+            physics: game.physics,
+            script: game.script.clone(),
+            match_score: game.match_score.clone(),
+            match_time_left: game.players.iter().map(|player| player.match_time_left).collect(),
+            breaker: game.breaker,
+            rack_seed: game.rack_seed,
+            inputs: Vec::new(),
+        }
+    }
+
+    // Replay do rack em andamento (ou do último, depois do fim do rack)
+    pub fn of_rack(game: &Game) -> Option<Replay> {
+        game.rack_recording.clone()
+    }
+
+    // Arma o jogo da saída: uma partida nova com as mesmas opções, o placar e o tempo
+    // que vinham de antes e o rack tirado da semente gravada
+    pub fn start_game(&self) -> Game {
+        let mut game = Game::new();
+        game.variant = self.variant;
+        game.table = self.table.clone();
+        game.player_count = self.player_count;
+        game.house_rules = self.house_rules;
+        game.time_control = self.time_control;
+        game.match_settings = MatchSettings { lag: false, ..self.match_settings };
+        game.rack_options = RackOptions { seed: Some(self.rack_seed), ..self.rack_options };
+        game.physics = self.physics;
+        game.script = self.script.clone();
+        game.new_match();
+        game.match_score = self.match_score.clone();
+        for (player, &time_left) in game.players.iter_mut().zip(&self.match_time_left) {
+            player.match_time_left = time_left;
+        }
+        game.notifications.clear();
+        game.start_rack(self.breaker);
+        game
+    }
+
+    pub fn shot_count(&self) -> usize {
+        self.inputs.iter().filter(|input| matches!(input, ReplayInput::Shoot { .. })).count()
+    }
+}
+
+impl Game {
+    pub(crate) fn record_input(&mut self, input: ReplayInput) {
+        if let Some(recording) = self.rack_recording.as_mut() {
+            recording.inputs.push(input);
+        }
+    }
+
+    // Começa a gravação do rack; chamado no fim de start_rack
+    pub(crate) fn begin_rack_recording(&mut self) {
+        self.rack_recording = Some(Replay::starting(self));
+    }
+
+    // As entradas gravadas foram aceitas ao vivo, então aqui o resultado não importa
+    fn apply_replay_input(&mut self, input: ReplayInput) {
+        let command = match input {
+            ReplayInput::PlaceCueBall(pos) => GameCommand::PlaceCueBall(pos),
+            ReplayInput::CallBall(number) => GameCommand::CallBall(number),
+            ReplayInput::CallPocket(pocket) => GameCommand::CallPocket(pocket),
+            ReplayInput::Shoot { angle, power } => GameCommand::Shoot { angle, power },
+            ReplayInput::Rerack => GameCommand::Rerack,
+            ReplayInput::AcceptTable => GameCommand::AcceptTable,
+            ReplayInput::ChooseGroup(group) => GameCommand::ChooseGroup(group),
+            ReplayInput::UseExtension => GameCommand::UseExtension,
+            ReplayInput::ShotClockExpired => return self.handle_shot_clock_expired(),
+            ReplayInput::MatchTimeExpired => return self.handle_match_time_expired(),
+        };
+        let _ = self.execute(command);
+    }
+
+    pub fn balls_in_motion(&self) -> bool {
+        matches!(self.game_state, GameState::Shooting | GameState::BallsMoving)
+    }
+}
+
+// Velocidades oferecidas no controle de replay, em quadros de física por quadro de tela
+pub const REPLAY_SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
+// Reprodução de um replay. O Game reproduzido fica com quem chama (o front end
+// desenha o mesmo tipo de jogo ao vivo ou em replay); `start_game` dá o estado inicial,
+// armado uma vez só para as voltas ao começo.
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    start: Game,
+    pub playing: bool,
+    pub speed: f32,
+    next_input: usize,
+    frame_budget: f32,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        let start = replay.start_game();
+        ReplayPlayer { replay, start, playing: true, speed: 1.0, next_input: 0, frame_budget: 0.0 }
+    }
+
+    pub fn start_game(&self) -> Game {
+        self.start.clone()
+    }
+
+    // Tacadas já batidas (a que está rolando conta)
+    pub fn shots_taken(&self) -> usize {
+        self.replay.inputs[..self.next_input].iter()
+            .filter(|input| matches!(input, ReplayInput::Shoot { .. }))
+            .count()
+    }
+
+    pub fn is_finished(&self, game: &Game) -> bool {
+        self.next_input >= self.replay.inputs.len() && !game.balls_in_motion()
+    }
+
+    pub fn next_speed(&mut self) {
+        let idx = REPLAY_SPEEDS.iter().position(|&speed| speed == self.speed).unwrap_or(0);
+        self.speed = REPLAY_SPEEDS[(idx + 1) % REPLAY_SPEEDS.len()];
+    }
+
+    // Avança um quadro de tela: `speed` quadros de física, cada um rodando a física
+    // ou, com as bolas paradas, aplicando a próxima entrada
+    pub fn update(&mut self, game: &mut Game) {
+        if !self.playing { return; }
+        self.frame_budget += self.speed;
+        while self.frame_budget >= 1.0 {
+            self.frame_budget -= 1.0;
+            if !self.step(game) {
+                self.playing = false;
+                self.frame_budget = 0.0;
+                return;
+            }
+        }
+    }
+
+    // Um passo do replay; false quando não há mais nada a fazer
+    fn step(&mut self, game: &mut Game) -> bool {
+        if game.balls_in_motion() {
+            game.update_physics_objects();
+            return true;
+        }
+        let Some(&input) = self.replay.inputs.get(self.next_input) else { return false; };
+        self.next_input += 1;
+        game.apply_replay_input(input);
+        true
+    }
+
+    // Tacada anterior: com a bola rolando volta ao começo dela, parado volta uma
+    pub fn previous_shot(&mut self, game: &mut Game) {
+        let target = self.shots_taken().saturating_sub(1);
+        self.seek(game, target);
+    }
+
+    // Próxima tacada: termina a que está rolando ou pula a que viria
+    pub fn next_shot(&mut self, game: &mut Game) {
+        let target = if game.balls_in_motion() { self.shots_taken() } else { self.shots_taken() + 1 };
+        self.seek(game, target);
+    }
+
+    // Volta ao começo do rack e rejoga sem pausa até a tacada `shot` (contando de 0)
+    // estar para ser batida, com as bolas paradas
+    pub fn seek(&mut self, game: &mut Game, shot: usize) {
+        *game = self.start_game();
+        self.next_input = 0;
+        self.frame_budget = 0.0;
+        loop {
+            let next_is_target = matches!(self.replay.inputs.get(self.next_input), Some(ReplayInput::Shoot { .. }))
+                && self.shots_taken() == shot;
+            if (next_is_target && !game.balls_in_motion()) || !self.step(game) {
+                break;
+            }
+        }
+        // Os avisos das tacadas puladas não valem para a posição nova
+        game.notifications.clear();
+    }
+}
+
+// played também serve aos testes do save
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Replay, ReplayPlayer};
+    use crate::Game;
+    use crate::state_machine::GameCommand;
+    use crate::types::{GameState, GameVariant, PlayerGroup};
+
+    // Algumas tacadas de verdade, com a física, decidindo o que a mesa pedir
+    pub(crate) fn played(variant: GameVariant) -> Game {
+        let mut game = Game::new();
+        game.variant = variant;
+        game.rack_seed_state = 7;
+        game.new_match();
+        for shot in 0..6 {
+            match game.game_state() {
+                GameState::RepositionCueBall => game.execute(GameCommand::PlaceCueBall(game.head_spot() * 0.9)).unwrap(),
+                GameState::BreakChoice => game.execute(GameCommand::AcceptTable).unwrap(),
+                GameState::GroupChoice => game.execute(GameCommand::ChooseGroup(PlayerGroup::Solids)).unwrap(),
+                _ => {}
+            }
+            if game.game_state() != GameState::Aiming { break; }
+            game.execute(GameCommand::Shoot { angle: 0.15 * shot as f32, power: 0.8 }).unwrap();
+            while game.balls_in_motion() {
+                game.update_physics_objects();
+            }
+        }
+        game
+    }
+
+    fn assert_same_game(live: &Game, replayed: &Game) {
+        let positions = |game: &Game| game.balls.iter().map(|ball| (ball.number, ball.pos, ball.in_pocket)).collect::<Vec<_>>();
+        let scores = |game: &Game| game.players.iter().map(|player| (player.score, player.group)).collect::<Vec<_>>();
+        assert_eq!(positions(replayed), positions(live));
+        assert_eq!(scores(replayed), scores(live));
+        assert_eq!(replayed.current_player, live.current_player);
+        assert_eq!(replayed.game_state(), live.game_state());
+    }
+
+    fn run_to_end(player: &mut ReplayPlayer, game: &mut Game) {
+        while !player.is_finished(game) {
+            player.playing = true;
+            player.update(game);
+        }
+    }
+
+    #[test]
+    fn replay_reaches_the_same_game() {
+        for variant in [GameVariant::EightBall, GameVariant::StraightPool, GameVariant::Snooker, GameVariant::ThreeCushion, GameVariant::Cutthroat] {
+            let live = played(variant);
+            let replay = Replay::of_rack(&live).unwrap();
+            assert!(replay.shot_count() > 0);
+            let mut player = ReplayPlayer::new(replay);
+            let mut game = player.start_game();
+            player.speed = 4.0;
+            run_to_end(&mut player, &mut game);
+            assert_same_game(&live, &game);
+        }
+    }
+
+    #[test]
+    fn start_game_is_the_rack_as_it_was_racked() {
+        let live = played(GameVariant::EightBall);
+        let start = Replay::of_rack(&live).unwrap().start_game();
+        let mut fresh = Game::new();
+        fresh.rack_seed_state = 7;
+        fresh.new_match();
+        assert_same_game(&fresh, &start);
+        assert_eq!(start.rack_seed, live.rack_seed);
+    }
+
+    #[test]
+    fn seeking_back_and_playing_on_reaches_the_same_game() {
+        let live = played(GameVariant::StraightPool);
+        let mut player = ReplayPlayer::new(Replay::of_rack(&live).unwrap());
+        let mut game = player.start_game();
+        player.seek(&mut game, 3);
+        assert_eq!(player.shots_taken(), 3);
+        player.previous_shot(&mut game);
+        assert_eq!(player.shots_taken(), 2);
+        run_to_end(&mut player, &mut game);
+        assert_same_game(&live, &game);
+    }
+}
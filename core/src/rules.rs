@@ -0,0 +1,564 @@
+use crate::Game; // Precisa de acesso a quase tudo de Game
+use crate::constants::{CONSECUTIVE_FOUL_LIMIT, LEGAL_BREAK_MIN_RAIL_BALLS};
+use crate::types::{Foul, PlayerGroup, PlayerId, GameState, GameVariant, PlacementArea};
+use crate::replay::ReplayInput;
+use crate::notification::{LossReason, Notification};
+
+impl Game {
+    // handle_player_turn_end, check_eight_ball_pot_legality, update_player_groups_if_only_eight_ball_left
+    // Movidos para cá e implementados como métodos de Game.
+    // Atenção: eles precisam de acesso a `self`, então são `pub fn method_name(&mut self)`.
+
+    pub fn handle_player_turn_end(&mut self) {
+        if self.is_lagging() {
+            self.handle_lag_shot_end();
+            return;
+        }
+        match self.variant {
+            GameVariant::EightBall => self.handle_eight_ball_turn_end(),
+            GameVariant::StraightPool => self.handle_straight_pool_turn_end(),
+            GameVariant::Snooker => self.handle_snooker_turn_end(),
+            GameVariant::Blackball => self.handle_blackball_turn_end(),
+            GameVariant::ThreeCushion => self.handle_three_cushion_turn_end(),
+            GameVariant::Cutthroat => self.handle_cutthroat_turn_end(),
+            GameVariant::Scripted => self.handle_scripted_turn_end(),
+        }
+        self.called_pocket = None;
+    }
+
+    fn handle_eight_ball_turn_end(&mut self) {
+        let cue_ball_is_pocketed = self.cue_ball_idx.is_none_or(|idx| self.balls[idx].in_pocket);
+        let mut player_continues_turn = false;
+        let mut game_over_by_eight_ball = false;
+
+        let mut current_player_group = self.current().group;
+        let was_break_shot = self.is_break_shot;
+
+        if was_break_shot && self.handle_eight_ball_break(cue_ball_is_pocketed) {
+            return;
+        }
+
+        // Regra da casa: quem está na 8 e encaçapa a branca junto não perde; a 8
+        // volta para a mesa e a jogada vira uma falta comum
+        if self.potted_ball_numbers_this_turn.contains(&8) && cue_ball_is_pocketed
+            && current_player_group == PlayerGroup::EightBall && !self.house_rules.scratch_on_eight_loses
+        {
+            self.respot_eight_ball();
+        }
+
+        if self.potted_ball_numbers_this_turn.contains(&8) {
+            game_over_by_eight_ball = true;
+            let winner = match self.check_eight_ball_pot_legality(current_player_group, cue_ball_is_pocketed) {
+                Ok(winner) => winner,
+                Err(reason) => {
+                    self.notify(Notification::Lost { player: self.current_player, reason });
+                    self.next_player()
+                }
+            };
+            self.notify(Notification::Won { player: winner });
+            self.end_rack(winner);
+        }
+
+        if game_over_by_eight_ball {
+            self.potted_ball_numbers_this_turn.clear();
+            return;
+        }
+
+        let shooter = self.current_player;
+        // Na saída só a branca encaçapada é falta; o resto já foi visto acima
+        let foul = if was_break_shot {
+            cue_ball_is_pocketed.then_some(Foul::Scratch)
+        } else {
+            self.eight_ball_foul(current_player_group, cue_ball_is_pocketed)
+        };
+        self.shot_foul = foul;
+        let fouls = if self.three_foul_rule() { self.track_foul(shooter, foul.is_some()) } else { 0 };
+        if let Some(foul) = foul {
+            self.notify(Notification::Foul { player: shooter, reason: foul });
+        }
+        if fouls >= CONSECUTIVE_FOUL_LIMIT {
+            let winner = self.next_player();
+            self.notify(Notification::Lost { player: shooter, reason: LossReason::ThirdFoul });
+            self.notify(Notification::Won { player: winner });
+            self.end_rack(winner);
+            return;
+        }
+        if fouls == CONSECUTIVE_FOUL_LIMIT - 1 {
+            self.notify(Notification::FoulWarning { player: shooter });
+        }
+
+        // Bola na mão: sempre na branca encaçapada e, com a regra de bola na mão em
+        // qualquer lugar, em toda falta. Depois da saída só vale a área de saída.
+        let ball_in_hand = cue_ball_is_pocketed
+            || (foul.is_some() && self.house_rules.ball_in_hand == PlacementArea::Anywhere);
+        if ball_in_hand {
+            self.ball_in_hand_area = if was_break_shot { PlacementArea::Kitchen } else { self.house_rules.ball_in_hand };
+            self.set_state(GameState::RepositionCueBall);
+        } else if foul.is_none() {
+            let mut solid_potted_count = 0;
+            let mut stripe_potted_count = 0;
+            let mut first_potted_ball_type: Option<PlayerGroup> = None;
+
+            for &ball_num in &self.potted_ball_numbers_this_turn {
+                if ball_num == 8 { continue; }
+                let def = self.ball_definitions_map.get(&ball_num);
+                if let Some(d) = def {
+                    if d.is_striped {
+                        stripe_potted_count +=1;
+                        if first_potted_ball_type.is_none() { first_potted_ball_type = Some(PlayerGroup::Stripes); }
+                    } else {
+                        solid_potted_count +=1;
+                        if first_potted_ball_type.is_none() { first_potted_ball_type = Some(PlayerGroup::Solids); }
+                    }
+                }
+            }
+
+            if current_player_group == PlayerGroup::Undecided {
+                let potted_any = !self.potted_ball_numbers_this_turn.is_empty();
+                if was_break_shot && self.house_rules.open_table_after_break && potted_any {
+                    self.notify(Notification::OpenTable { after_break: true });
+                    player_continues_turn = true;
+                } else if was_break_shot && self.house_rules.choose_group_after_mixed_break
+                    && solid_potted_count > 0 && stripe_potted_count > 0
+                {
+                    self.is_break_shot = false;
+                    self.set_state(GameState::GroupChoice);
+                    self.notify(Notification::GroupChoice { player: self.current_player });
+                    return;
+                } else if let Some(potted_type) = first_potted_ball_type {
+                    self.assign_groups(self.current_player, potted_type);
+                    current_player_group = potted_type;
+                    self.notify(Notification::GroupAssigned { player: self.current_player, group: current_player_group });
+                    player_continues_turn = true;
+                } else if !self.potted_ball_numbers_this_turn.is_empty() {
+                    self.notify(Notification::OpenTable { after_break: false });
+                    player_continues_turn = true;
+                } else {
+                    self.notify(Notification::NothingPotted);
+                }
+            } else {
+                let potted_own_ball = match current_player_group {
+                    PlayerGroup::Solids => solid_potted_count > 0,
+                    PlayerGroup::Stripes => stripe_potted_count > 0,
+                    _ => false,
+                };
+
+                if potted_own_ball {
+                    self.notify(Notification::GoodShot);
+                    player_continues_turn = true;
+                } else {
+                    if !self.potted_ball_numbers_this_turn.is_empty() {
+                        self.notify(Notification::WrongBallPotted);
+                    } else {
+                        self.notify(Notification::NoOwnBall);
+                    }
+                }
+            }
+        }
+        
+        self.is_break_shot = false;
+
+        if !player_continues_turn || cue_ball_is_pocketed {
+            self.advance_turn();
+        }
+
+        if self.game_state == GameState::RepositionCueBall {
+            self.notify_ball_in_hand();
+        } else {
+            self.set_state(GameState::Aiming);
+            self.notify(if player_continues_turn {
+                Notification::PlaysAgain { player: self.current_player }
+            } else {
+                Notification::TurnChanged { player: self.current_player }
+            });
+        }
+        
+        if !matches!(self.game_state, GameState::GameOver | GameState::MatchOver) {
+             self.update_player_groups_if_only_eight_ball_left();
+        }
+    }
+
+    fn eight_ball_foul(&self, group: PlayerGroup, cue_ball_is_pocketed: bool) -> Option<Foul> {
+        if cue_ball_is_pocketed {
+            return Some(Foul::Scratch);
+        }
+        let Some(first_contact) = self.shot_log.first_contact(0) else {
+            return Some(Foul::NoContact);
+        };
+        let legal_first_contact = match group {
+            PlayerGroup::Undecided => first_contact != 8,
+            PlayerGroup::EightBall => first_contact == 8,
+            _ => self.ball_group(first_contact) == Some(group),
+        };
+        if !legal_first_contact {
+            return Some(Foul::WrongBallFirst);
+        }
+        if self.house_rules.opponent_ball_is_foul
+            && matches!(group, PlayerGroup::Solids | PlayerGroup::Stripes)
+            && self.potted_ball_numbers_this_turn.iter().any(|&number| self.ball_group(number) == Some(self.opposite_group(group)))
+        {
+            return Some(Foul::WrongBallPotted);
+        }
+        if self.potted_ball_numbers_this_turn.is_empty() && !self.shot_log.cushion_after_first_contact(0) {
+            return Some(Foul::NoRail);
+        }
+        None
+    }
+
+    // Saída do bola 8. A 8 encaçapada na saída volta para a mesa ou dá a vitória,
+    // conforme a regra configurada. Sem bola encaçapada, pelo menos quatro bolas
+    // precisam chegar a uma tabela; se não, o adversário escolhe entre rearmar e
+    // sair ou aceitar a mesa. Retorna true quando a jogada já foi resolvida aqui.
+    fn handle_eight_ball_break(&mut self, cue_ball_is_pocketed: bool) -> bool {
+        let potted_any = !self.potted_ball_numbers_this_turn.is_empty();
+
+        if self.potted_ball_numbers_this_turn.contains(&8) {
+            if self.house_rules.eight_on_break_wins && !cue_ball_is_pocketed {
+                self.notify(Notification::EightOnBreak);
+                self.notify(Notification::Won { player: self.current_player });
+                self.end_rack(self.current_player);
+                return true;
+            }
+            self.respot_eight_ball();
+        }
+
+        let balls_to_rail = self.shot_log.distinct_balls_to_cushion(&[0]);
+        if !potted_any && balls_to_rail < LEGAL_BREAK_MIN_RAIL_BALLS {
+            self.shot_foul = Some(Foul::IllegalBreak);
+            self.advance_turn();
+            self.set_state(GameState::BreakChoice);
+            self.notify(Notification::IllegalBreak { rail_balls: balls_to_rail, chooser: self.current_player });
+            return true;
+        }
+        false
+    }
+
+    // Grupo escolhido depois de lisas e listradas na saída; quem escolheu continua
+    pub(crate) fn choose_group_after_break(&mut self, group: PlayerGroup) {
+        self.record_input(ReplayInput::ChooseGroup(group));
+        self.assign_groups(self.current_player, group);
+        self.set_state(GameState::Aiming);
+        self.notify(Notification::GroupAssigned { player: self.current_player, group });
+    }
+
+    // Com a regra de cantar a 8, quem está nela escolhe a caçapa antes da tacada
+    pub fn must_call_eight(&self) -> bool {
+        self.variant == GameVariant::EightBall
+            && self.house_rules.call_the_eight
+            && self.current().group == PlayerGroup::EightBall
+    }
+
+    fn respot_eight_ball(&mut self) {
+        self.potted_ball_numbers_this_turn.retain(|&number| number != 8);
+        self.current_mut().pocketed_balls.retain(|def| def.number != 8);
+        self.spot_ball_number(8);
+        self.notify(Notification::EightRespotted);
+    }
+
+    // Depois da saída ilegal, quem escolhe rearmar sai de novo com as bolas no triângulo
+    pub(crate) fn rerack_after_illegal_break(&mut self) {
+        self.record_input(ReplayInput::Rerack);
+        self.draw_rack_seed();
+        self.setup_balls();
+        self.clear_pocketed_balls();
+        self.shot_log.clear();
+        self.potted_ball_numbers_this_turn.clear();
+        self.is_break_shot = true;
+        self.set_state(GameState::Aiming);
+        self.notify(Notification::Reracked { breaker: self.current_player });
+    }
+
+    // Aceitando a mesa o jogo segue de onde parou; com a branca encaçapada na saída
+    // ela vai para trás da linha de saída
+    pub(crate) fn accept_table_after_illegal_break(&mut self) {
+        self.record_input(ReplayInput::AcceptTable);
+        self.is_break_shot = false;
+        if self.cue_ball_idx.is_none_or(|idx| self.balls[idx].in_pocket) {
+            self.notify(Notification::TableAccepted { player: self.current_player });
+            self.set_state(GameState::RepositionCueBall);
+            self.notify_ball_in_hand();
+        } else {
+            self.set_state(GameState::Aiming);
+            self.notify(Notification::TableAccepted { player: self.current_player });
+        }
+    }
+    
+    // Vencedor quando a 8 caiu legalmente; senão, por que o jogador da vez perde
+    pub fn check_eight_ball_pot_legality(&self, player_group_before_pot: PlayerGroup, cue_ball_was_pocketed: bool) -> Result<PlayerId, LossReason> {
+        if cue_ball_was_pocketed {
+            return Err(LossReason::EightAndScratch);
+        }
+
+        if player_group_before_pot == PlayerGroup::Undecided {
+            return Err(LossReason::EightOnOpenTable);
+        }
+        
+        if player_group_before_pot != PlayerGroup::EightBall && self.balls_left_in_group(player_group_before_pot) > 0 {
+            return Err(LossReason::EightTooEarly);
+        }
+
+        if self.must_call_eight() && self.called_pocket != self.shot_log.pocket_of(8) {
+            Err(LossReason::EightWrongPocket)
+        } else {
+            Ok(self.current_player)
+        }
+    }
+
+    // O jogador fica com o grupo e todos os outros com o grupo oposto
+    pub fn assign_groups(&mut self, player: PlayerId, group: PlayerGroup) {
+        let other_group = self.opposite_group(group);
+        for id in self.player_ids() {
+            self.player_mut(id).group = if id == player { group } else { other_group };
+        }
+    }
+
+    // Grupo a que pertence uma bola numerada: 1 a 7 de um lado, 9 a 15 do outro
+    pub fn ball_group(&self, number: u8) -> Option<PlayerGroup> {
+        let (low, high) = match self.variant {
+            GameVariant::Blackball => (PlayerGroup::Reds, PlayerGroup::Yellows),
+            GameVariant::Cutthroat => return self.cutthroat_range_of(number),
+            _ => (PlayerGroup::Solids, PlayerGroup::Stripes),
+        };
+        match number {
+            1..=7 => Some(low),
+            9..=15 => Some(high),
+            _ => None,
+        }
+    }
+
+    pub fn opposite_group(&self, group: PlayerGroup) -> PlayerGroup {
+        match group {
+            PlayerGroup::Solids => PlayerGroup::Stripes,
+            PlayerGroup::Stripes => PlayerGroup::Solids,
+            PlayerGroup::Reds => PlayerGroup::Yellows,
+            PlayerGroup::Yellows => PlayerGroup::Reds,
+            other => other,
+        }
+    }
+
+    // Bolas do grupo que ainda estão na mesa
+    pub fn balls_left_in_group(&self, group: PlayerGroup) -> usize {
+        self.balls.iter()
+            .filter(|ball| !ball.in_pocket && self.ball_group(ball.number) == Some(group))
+            .count()
+    }
+
+    pub fn update_player_groups_if_only_eight_ball_left(&mut self) {
+        for id in self.player_ids() {
+            let group = self.player(id).group;
+            if !matches!(group, PlayerGroup::Undecided | PlayerGroup::EightBall)
+                && self.balls_left_in_group(group) == 0
+            {
+                self.player_mut(id).group = PlayerGroup::EightBall;
+            }
+        }
+        
+        // Só avisa quem vai mirar agora; depois de falta o aviso é a falta
+        if self.current().group == PlayerGroup::EightBall
+            && self.game_state == GameState::Aiming && self.shot_foul.is_none()
+        {
+            self.notify(Notification::OnTheEight { player: self.current_player });
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::Game;
+    use crate::house_rules::HouseRules;
+    use crate::notification::{LossReason, Notification};
+    use crate::shot::ShotEvent;
+    use crate::types::{Foul, GameState, PlacementArea, PlayerGroup, PlayerId};
+
+    // Bolas paradas depois de uma tacada do jogador 1, ainda na saída
+    fn eight_ball(house_rules: HouseRules) -> Game {
+        let mut game = Game::new();
+        game.house_rules = house_rules;
+        game.new_match();
+        game.game_state = GameState::BallsMoving;
+        game.take_notifications();
+        game
+    }
+
+    fn after_break(house_rules: HouseRules) -> Game {
+        let mut game = eight_ball(house_rules);
+        game.is_break_shot = false;
+        game
+    }
+
+    fn pot(game: &mut Game, number: u8, pocket: usize) {
+        let idx = game.balls.iter().position(|ball| ball.number == number).unwrap();
+        game.balls[idx].in_pocket = true;
+        if number != 0 {
+            game.potted_ball_numbers_this_turn.push(number);
+        }
+        game.shot_log.record(ShotEvent::Pocketed(number, pocket));
+    }
+
+    fn rails(game: &mut Game, numbers: &[u8]) {
+        for &number in numbers {
+            game.shot_log.record(ShotEvent::Cushion(number));
+        }
+    }
+
+    #[test]
+    fn break_with_too_few_balls_to_the_rail_gives_the_opponent_the_choice() {
+        let mut game = eight_ball(HouseRules::CLASSIC);
+        game.shot_log.record(ShotEvent::BallContact(0, 1));
+        rails(&mut game, &[1, 2, 3]);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.game_state, GameState::BreakChoice);
+        assert_eq!(game.shot_foul, Some(Foul::IllegalBreak));
+        assert_eq!(game.current_player, PlayerId(1));
+        assert!(game.notifications.contains(&Notification::IllegalBreak { rail_balls: 3, chooser: PlayerId(1) }));
+    }
+
+    #[test]
+    fn break_with_four_balls_to_the_rail_is_legal() {
+        let mut game = eight_ball(HouseRules::CLASSIC);
+        game.shot_log.record(ShotEvent::BallContact(0, 1));
+        rails(&mut game, &[1, 2, 3, 4]);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.game_state, GameState::Aiming);
+        assert_eq!(game.shot_foul, None);
+        assert_eq!(game.current_player, PlayerId(1));
+        assert!(!game.is_break_shot);
+    }
+
+    #[test]
+    fn scratch_on_the_break_gives_ball_in_hand_in_the_kitchen_even_with_anywhere_rules() {
+        let mut game = eight_ball(HouseRules::OFFICIAL);
+        game.shot_log.record(ShotEvent::BallContact(0, 1));
+        pot(&mut game, 3, 0);
+        pot(&mut game, 0, 1);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.shot_foul, Some(Foul::Scratch));
+        assert_eq!(game.game_state, GameState::RepositionCueBall);
+        assert_eq!(game.ball_in_hand_area, PlacementArea::Kitchen);
+        assert_eq!(game.current_player, PlayerId(1));
+    }
+
+    #[test]
+    fn eight_on_the_break_wins_under_bar_rules() {
+        let mut game = eight_ball(HouseRules::BAR);
+        game.shot_log.record(ShotEvent::BallContact(0, 1));
+        pot(&mut game, 8, 2);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.match_winner(), Some(PlayerId(0)));
+        assert!(game.notifications.contains(&Notification::EightOnBreak));
+    }
+
+    #[test]
+    fn eight_on_the_break_is_respotted_under_classic_rules() {
+        let mut game = eight_ball(HouseRules::CLASSIC);
+        game.shot_log.record(ShotEvent::BallContact(0, 1));
+        pot(&mut game, 8, 2);
+        game.handle_player_turn_end();
+
+        let eight = game.balls.iter().find(|ball| ball.number == 8).unwrap();
+        assert!(!eight.in_pocket);
+        assert_eq!(game.match_winner(), None);
+        assert!(game.notifications.contains(&Notification::EightRespotted));
+    }
+
+    #[test]
+    fn first_ball_potted_on_an_open_table_assigns_the_groups() {
+        let mut game = after_break(HouseRules::CLASSIC);
+        game.shot_log.record(ShotEvent::BallContact(0, 3));
+        pot(&mut game, 3, 0);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.players[0].group, PlayerGroup::Solids);
+        assert_eq!(game.players[1].group, PlayerGroup::Stripes);
+        assert_eq!(game.current_player, PlayerId(0));
+        assert_eq!(game.game_state, GameState::Aiming);
+    }
+
+    #[test]
+    fn hitting_the_opponents_ball_first_is_a_foul() {
+        let mut game = after_break(HouseRules::OFFICIAL);
+        game.assign_groups(PlayerId(0), PlayerGroup::Solids);
+        game.shot_log.record(ShotEvent::BallContact(0, 9));
+        rails(&mut game, &[9]);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.shot_foul, Some(Foul::WrongBallFirst));
+        assert_eq!(game.current_player, PlayerId(1));
+        assert_eq!(game.game_state, GameState::RepositionCueBall);
+        assert_eq!(game.ball_in_hand_area, PlacementArea::Anywhere);
+    }
+
+    #[test]
+    fn foul_without_scratch_keeps_the_cue_ball_in_place_under_classic_rules() {
+        let mut game = after_break(HouseRules::CLASSIC);
+        game.assign_groups(PlayerId(0), PlayerGroup::Solids);
+        game.shot_log.record(ShotEvent::BallContact(0, 2));
+        game.handle_player_turn_end();
+
+        assert_eq!(game.shot_foul, Some(Foul::NoRail));
+        assert_eq!(game.current_player, PlayerId(1));
+        assert_eq!(game.game_state, GameState::Aiming);
+    }
+
+    #[test]
+    fn missing_every_ball_is_a_foul() {
+        let mut game = after_break(HouseRules::CLASSIC);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.shot_foul, Some(Foul::NoContact));
+        assert_eq!(game.current_player, PlayerId(1));
+    }
+
+    #[test]
+    fn third_foul_in_a_row_loses_with_the_rule_on() {
+        let mut game = after_break(HouseRules::BAR);
+        game.players[0].consecutive_fouls = 2;
+        game.handle_player_turn_end();
+
+        assert_eq!(game.match_winner(), Some(PlayerId(1)));
+        assert!(game.notifications.contains(&Notification::Lost { player: PlayerId(0), reason: LossReason::ThirdFoul }));
+    }
+
+    #[test]
+    fn eight_before_clearing_the_group_loses() {
+        let mut game = after_break(HouseRules::CLASSIC);
+        game.assign_groups(PlayerId(0), PlayerGroup::Solids);
+        game.shot_log.record(ShotEvent::BallContact(0, 8));
+        pot(&mut game, 8, 0);
+        game.handle_player_turn_end();
+
+        assert_eq!(game.match_winner(), Some(PlayerId(1)));
+        assert!(game.notifications.contains(&Notification::Lost { player: PlayerId(0), reason: LossReason::EightTooEarly }));
+    }
+
+    #[test]
+    fn eight_with_the_cue_ball_loses() {
+        let game = after_break(HouseRules::CLASSIC);
+        assert_eq!(game.check_eight_ball_pot_legality(PlayerGroup::EightBall, true), Err(LossReason::EightAndScratch));
+        assert_eq!(game.check_eight_ball_pot_legality(PlayerGroup::Undecided, false), Err(LossReason::EightOnOpenTable));
+    }
+
+    #[test]
+    fn eight_after_the_group_wins_and_needs_the_called_pocket() {
+        let mut game = after_break(HouseRules::OFFICIAL);
+        game.assign_groups(PlayerId(0), PlayerGroup::Solids);
+        for number in 1..=7 {
+            pot(&mut game, number, 0);
+        }
+        game.potted_ball_numbers_this_turn.clear();
+        game.shot_log.clear();
+        game.update_player_groups_if_only_eight_ball_left();
+        assert_eq!(game.players[0].group, PlayerGroup::EightBall);
+
+        game.called_pocket = Some(3);
+        pot(&mut game, 8, 2);
+        assert_eq!(game.check_eight_ball_pot_legality(PlayerGroup::EightBall, false), Err(LossReason::EightWrongPocket));
+        game.called_pocket = Some(2);
+        assert_eq!(game.check_eight_ball_pot_legality(PlayerGroup::EightBall, false), Ok(PlayerId(0)));
+    }
+}
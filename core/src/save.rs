@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Game;
+use crate::replay::Replay;
+
+// Versão do formato do save. Sobe sempre que o estado gravado mudar de forma
+// incompatível; saves de outra versão são recusados em vez de carregados pela metade.
+pub const SAVE_VERSION: u32 = 4;
+
+#[derive(Serialize)]
+struct SaveFileRef<'a> {
+    version: u32,
+    game: &'a Game,
+}
+
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct SaveFile {
+    game: Game,
+}
+
+// O replay leva as opções da partida no formato do save, então segue a mesma versão
+#[derive(Serialize)]
+struct ReplayFileRef<'a> {
+    version: u32,
+    replay: &'a Replay,
+}
+
+#[derive(Deserialize)]
+struct ReplayFile {
+    replay: Replay,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Format(String),
+    UnsupportedVersion(u32),
+}
+
+// Partida inteira em JSON, com a versão do formato
+pub fn save_game(game: &Game) -> Result<String, SaveError> {
+    serde_json::to_string(&SaveFileRef { version: SAVE_VERSION, game })
+        .map_err(|e| SaveError::Format(e.to_string()))
+}
+
+pub fn load_game(text: &str) -> Result<Game, SaveError> {
+    check_version(text)?;
+    let file: SaveFile = serde_json::from_str(text).map_err(|e| SaveError::Format(e.to_string()))?;
+    Ok(file.game)
+}
+
+fn check_version(text: &str) -> Result<(), SaveError> {
+    let header: SaveHeader = serde_json::from_str(text).map_err(|e| SaveError::Format(e.to_string()))?;
+    if header.version != SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(header.version));
+    }
+    Ok(())
+}
+
+pub fn save_replay(replay: &Replay) -> Result<String, SaveError> {
+    serde_json::to_string(&ReplayFileRef { version: SAVE_VERSION, replay })
+        .map_err(|e| SaveError::Format(e.to_string()))
+}
+
+pub fn load_replay(text: &str) -> Result<Replay, SaveError> {
+    check_version(text)?;
+    let file: ReplayFile = serde_json::from_str(text).map_err(|e| SaveError::Format(e.to_string()))?;
+    Ok(file.replay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_game, load_replay, save_game, save_replay, SaveError, SAVE_VERSION};
+    use crate::replay::Replay;
+    use crate::replay::tests::played;
+    use crate::types::GameVariant;
+
+    // Compara o JSON já lido, que não depende da ordem das chaves dos mapas
+    fn json(text: &str) -> serde_json::Value {
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn game_survives_a_round_trip() {
+        let game = played(GameVariant::EightBall);
+        let text = save_game(&game).unwrap();
+        let loaded = load_game(&text).unwrap();
+        assert_eq!(json(&save_game(&loaded).unwrap()), json(&text));
+        assert_eq!(loaded.game_state(), game.game_state());
+        assert_eq!(loaded.current_player, game.current_player);
+    }
+
+    #[test]
+    fn replay_survives_a_round_trip() {
+        let game = played(GameVariant::Snooker);
+        let replay = Replay::of_rack(&game).unwrap();
+        let text = save_replay(&replay).unwrap();
+        let loaded = load_replay(&text).unwrap();
+        assert_eq!(loaded.inputs, replay.inputs);
+        assert_eq!(json(&save_replay(&loaded).unwrap()), json(&text));
+    }
+
+    #[test]
+    fn other_versions_are_refused() {
+        let text = save_game(&played(GameVariant::EightBall)).unwrap();
+        let current = format!("\"version\":{}", SAVE_VERSION);
+        assert!(text.contains(&current));
+        let old = text.replace(&current, "\"version\":3");
+        assert!(matches!(load_game(&old), Err(SaveError::UnsupportedVersion(3))));
+        let replay = save_replay(&Replay::of_rack(&played(GameVariant::EightBall)).unwrap()).unwrap();
+        assert!(matches!(load_replay(&replay.replace(&current, "\"version\":99")), Err(SaveError::UnsupportedVersion(99))));
+    }
+
+    #[test]
+    fn broken_file_is_a_format_error() {
+        assert!(matches!(load_game("{\"version\":"), Err(SaveError::Format(_))));
+        assert!(matches!(load_game(&format!("{{\"version\":{}}}", SAVE_VERSION)), Err(SaveError::Format(_))));
+    }
+}
@@ -0,0 +1,459 @@
+// Sem o recurso "scripting" quase nada daqui é usado: só o stub do engine no fim
+#![cfg_attr(not(feature = "scripting"), allow(dead_code))]
+
+use std::cell::OnceCell;
+use std::rc::Rc;
+
+use crate::Game;
+use crate::rack::RackRng;
+use crate::types::{Foul, GameState, PlacementArea, PlayerGroup, PlayerId};
+use crate::notification::{LossReason, Notification};
+
+// Variantes de regras escritas em Rhai. O script fica inteiro dentro do Game (e do
+// save e do replay), então uma partida com regras inventadas abre em qualquer lugar
+// que tenha o jogo, mesmo sem o arquivo da variante.
+//
+// O script define:
+//   fn rack()      opcional; devolve os números das bolas do triângulo, do ápice
+//                  para o fundo. `this` tem `balls`, `is_striped(n)` e `below(n)`
+//                  (sorteio pela semente do rack, para o rack se repetir).
+//   fn turn_end()  obrigatório; chamado quando as bolas param. `this` descreve a
+//                  tacada e recebe as decisões: foul, keep_turn, add_score,
+//                  set_group, win, lose, respot e ball_in_hand.
+// O que o script não decide segue o padrão: a vez passa e a branca encaçapada dá
+// bola na mão na área de saída.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScriptedVariant {
+    pub name: String,
+    pub source: String,
+    // Engine e AST montados na primeira chamada e divididos entre as cópias da
+    // variante; o save leva só o texto
+    #[cfg_attr(feature = "serde", serde(skip))]
+    compiled: OnceCell<Rc<Result<engine::Compiled, ScriptError>>>,
+}
+
+impl PartialEq for ScriptedVariant {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.source == other.source
+    }
+}
+
+impl ScriptedVariant {
+    // Compila o script e confere que ele tem turn_end
+    pub fn new(name: &str, source: &str) -> Result<Self, ScriptError> {
+        let variant = ScriptedVariant { name: name.to_string(), source: source.to_string(), compiled: OnceCell::new() };
+        engine::check(variant.compiled()?)?;
+        Ok(variant)
+    }
+
+    fn compiled(&self) -> Result<&engine::Compiled, ScriptError> {
+        self.compiled.get_or_init(|| Rc::new(engine::compile(&self.source)))
+            .as_ref()
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+// O que deu errado num script; o front end escreve a mensagem
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScriptError {
+    // Erro do próprio Rhai (sintaxe, limite de operações...), com o texto dele
+    Engine(String),
+    // Falta uma função obrigatória
+    Missing(String),
+    Player(i64),
+    Group(String),
+    Foul(String),
+    // rack() devolveu outra coisa que não números de bolas diferentes
+    Rack(String),
+    // Jogo compilado sem o recurso "scripting"
+    Disabled,
+}
+
+// A tacada como o script vê, e o que ele decidiu sobre ela
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ScriptTurn {
+    shooter: usize,
+    is_break: bool,
+    potted: Vec<u8>,
+    cue_potted: bool,
+    first_contact: Option<u8>,
+    rail_after_contact: bool,
+    rail_balls: usize,
+    balls_on_table: Vec<u8>,
+    striped: Vec<u8>,
+    groups: Vec<PlayerGroup>,
+    scores: Vec<i32>,
+    fouls: Vec<u32>,
+    eliminated: Vec<bool>,
+
+    foul: Option<Foul>,
+    keep_turn: bool,
+    ball_in_hand: bool,
+    points: Vec<(usize, i32)>,
+    new_groups: Vec<(usize, PlayerGroup)>,
+    respots: Vec<u8>,
+    winner: Option<usize>,
+    losers: Vec<usize>,
+}
+
+impl ScriptTurn {
+    fn player(&self, player: i64) -> Result<usize, ScriptError> {
+        usize::try_from(player).ok()
+            .filter(|&idx| idx < self.groups.len())
+            .ok_or(ScriptError::Player(player))
+    }
+
+    // Próximo jogador depois de quem tacou, pulando os eliminados
+    fn next_player(&self) -> usize {
+        let count = self.groups.len();
+        (1..=count)
+            .map(|step| (self.shooter + step) % count)
+            .find(|&idx| !self.eliminated[idx])
+            .unwrap_or(self.shooter)
+    }
+}
+
+// O rack como o script vê: as bolas da modalidade e o sorteio da semente do rack
+#[derive(Debug, Clone)]
+pub(crate) struct ScriptRack {
+    balls: Vec<u8>,
+    striped: Vec<u8>,
+    rng: RackRng,
+}
+
+fn group_name(group: PlayerGroup) -> &'static str {
+    match group {
+        PlayerGroup::Solids => "solids",
+        PlayerGroup::Stripes => "stripes",
+        PlayerGroup::Reds => "reds",
+        PlayerGroup::Yellows => "yellows",
+        PlayerGroup::EightBall => "eight",
+        PlayerGroup::Undecided | PlayerGroup::Range(..) => "undecided",
+    }
+}
+
+fn parse_group(name: &str) -> Result<PlayerGroup, ScriptError> {
+    match name {
+        "solids" => Ok(PlayerGroup::Solids),
+        "stripes" => Ok(PlayerGroup::Stripes),
+        "reds" => Ok(PlayerGroup::Reds),
+        "yellows" => Ok(PlayerGroup::Yellows),
+        "eight" => Ok(PlayerGroup::EightBall),
+        "undecided" => Ok(PlayerGroup::Undecided),
+        _ => Err(ScriptError::Group(name.to_string())),
+    }
+}
+
+fn parse_foul(name: &str) -> Result<Foul, ScriptError> {
+    match name {
+        "scratch" => Ok(Foul::Scratch),
+        "no_contact" => Ok(Foul::NoContact),
+        "no_rail" => Ok(Foul::NoRail),
+        "illegal_break" => Ok(Foul::IllegalBreak),
+        "wrong_ball_first" => Ok(Foul::WrongBallFirst),
+        "wrong_ball_potted" => Ok(Foul::WrongBallPotted),
+        _ => Err(ScriptError::Foul(name.to_string())),
+    }
+}
+
+#[cfg(feature = "scripting")]
+mod engine {
+    use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Position, Scope, AST, INT};
+    use super::{ScriptError, ScriptRack, ScriptTurn, group_name, parse_foul, parse_group};
+
+    // Limite de operações por chamada: um laço infinito no script vira erro em vez de travar o jogo
+    const MAX_OPERATIONS: u64 = 1_000_000;
+    // Profundidade das expressões, a mesma em debug e release (o padrão do Rhai muda com o build)
+    const MAX_EXPR_DEPTH: usize = 64;
+    const MAX_FUNCTION_EXPR_DEPTH: usize = 32;
+
+    type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+    // Erro de uma função do jogo chamada pelo script: vai como valor do erro do Rhai
+    // e volta intacto em script_error
+    impl From<ScriptError> for Box<EvalAltResult> {
+        fn from(error: ScriptError) -> Self {
+            EvalAltResult::ErrorRuntime(Dynamic::from(error), Position::NONE).into()
+        }
+    }
+
+    fn script_error(error: EvalAltResult) -> ScriptError {
+        match error {
+            EvalAltResult::ErrorRuntime(ref value, _) if value.is::<ScriptError>() => value.clone_cast(),
+            EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => script_error(*inner),
+            other => ScriptError::Engine(other.to_string()),
+        }
+    }
+
+    fn numbers(values: &[u8]) -> Array {
+        values.iter().map(|&n| Dynamic::from(n as INT)).collect()
+    }
+
+    fn engine() -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_FUNCTION_EXPR_DEPTH);
+
+        engine.register_type_with_name::<ScriptTurn>("Turn")
+            .register_get("shooter", |turn: &mut ScriptTurn| turn.shooter as INT)
+            .register_get("is_break", |turn: &mut ScriptTurn| turn.is_break)
+            .register_get("potted", |turn: &mut ScriptTurn| numbers(&turn.potted))
+            .register_get("cue_potted", |turn: &mut ScriptTurn| turn.cue_potted)
+            .register_get("first_contact", |turn: &mut ScriptTurn| turn.first_contact.map_or(-1, |n| n as INT))
+            .register_get("rail_after_contact", |turn: &mut ScriptTurn| turn.rail_after_contact)
+            .register_get("rail_balls", |turn: &mut ScriptTurn| turn.rail_balls as INT)
+            .register_get("balls_on_table", |turn: &mut ScriptTurn| numbers(&turn.balls_on_table))
+            .register_get("player_count", |turn: &mut ScriptTurn| turn.groups.len() as INT)
+            .register_fn("next_player", |turn: &mut ScriptTurn| turn.next_player() as INT)
+            .register_fn("is_striped", |turn: &mut ScriptTurn, number: INT| turn.striped.iter().any(|&n| n as INT == number))
+            .register_fn("group", |turn: &mut ScriptTurn, player: INT| -> ScriptResult<String> {
+                let idx = turn.player(player)?;
+                Ok(group_name(turn.groups[idx]).to_string())
+            })
+            .register_fn("score", |turn: &mut ScriptTurn, player: INT| -> ScriptResult<INT> {
+                let idx = turn.player(player)?;
+                Ok(turn.scores[idx] as INT)
+            })
+            .register_fn("fouls", |turn: &mut ScriptTurn, player: INT| -> ScriptResult<INT> {
+                let idx = turn.player(player)?;
+                Ok(turn.fouls[idx] as INT)
+            })
+            .register_fn("foul", |turn: &mut ScriptTurn, reason: &str| -> ScriptResult<()> {
+                turn.foul = Some(parse_foul(reason)?);
+                Ok(())
+            })
+            .register_fn("keep_turn", |turn: &mut ScriptTurn| turn.keep_turn = true)
+            .register_fn("ball_in_hand", |turn: &mut ScriptTurn| turn.ball_in_hand = true)
+            .register_fn("add_score", |turn: &mut ScriptTurn, player: INT, points: INT| -> ScriptResult<()> {
+                let idx = turn.player(player)?;
+                turn.points.push((idx, points as i32));
+                Ok(())
+            })
+            .register_fn("set_group", |turn: &mut ScriptTurn, player: INT, group: &str| -> ScriptResult<()> {
+                let idx = turn.player(player)?;
+                let group = parse_group(group)?;
+                turn.groups[idx] = group;
+                turn.new_groups.push((idx, group));
+                Ok(())
+            })
+            .register_fn("respot", |turn: &mut ScriptTurn, number: INT| turn.respots.push(number as u8))
+            .register_fn("win", |turn: &mut ScriptTurn, player: INT| -> ScriptResult<()> {
+                turn.winner = Some(turn.player(player)?);
+                Ok(())
+            })
+            .register_fn("lose", |turn: &mut ScriptTurn, player: INT| -> ScriptResult<()> {
+                let idx = turn.player(player)?;
+                turn.losers.push(idx);
+                Ok(())
+            });
+
+        engine.register_type_with_name::<ScriptRack>("Rack")
+            .register_get("balls", |rack: &mut ScriptRack| numbers(&rack.balls))
+            .register_fn("is_striped", |rack: &mut ScriptRack, number: INT| rack.striped.iter().any(|&n| n as INT == number))
+            .register_fn("below", |rack: &mut ScriptRack, n: INT| rack.rng.below(n.max(1) as usize) as INT);
+        engine
+    }
+
+    // O script pronto para chamar: o engine com as funções do jogo e o AST
+    #[derive(Debug)]
+    pub struct Compiled {
+        engine: Engine,
+        ast: AST,
+    }
+
+    impl Compiled {
+        fn has_function(&self, name: &str) -> bool {
+            self.ast.iter_functions().any(|f| f.name == name && f.params.is_empty())
+        }
+    }
+
+    pub fn compile(source: &str) -> Result<Compiled, ScriptError> {
+        let engine = engine();
+        let ast = engine.compile(source).map_err(|e| ScriptError::Engine(e.to_string()))?;
+        Ok(Compiled { engine, ast })
+    }
+
+    pub fn check(script: &Compiled) -> Result<(), ScriptError> {
+        if !script.has_function("turn_end") {
+            return Err(ScriptError::Missing("turn_end".to_string()));
+        }
+        Ok(())
+    }
+
+    // Chama `function` com `this` apontando para `value`; o script muda `value` no lugar
+    fn call<T: Clone + Send + Sync + 'static>(script: &Compiled, function: &str, value: T) -> Result<(T, Dynamic), ScriptError> {
+        let mut this = Dynamic::from(value);
+        let result = script.engine
+            .call_fn_with_options::<Dynamic>(CallFnOptions::new().bind_this_ptr(&mut this), &mut Scope::new(), &script.ast, function, ())
+            .map_err(|e| script_error(*e))?;
+        Ok((this.cast::<T>(), result))
+    }
+
+    pub fn turn_end(script: &Compiled, turn: ScriptTurn) -> Result<ScriptTurn, ScriptError> {
+        call(script, "turn_end", turn).map(|(turn, _)| turn)
+    }
+
+    // None quando o script não tem rack(); a ordem volta sem conferir as bolas
+    pub fn rack(script: &Compiled, rack: ScriptRack) -> Result<Option<(Vec<u8>, ScriptRack)>, ScriptError> {
+        if !script.has_function("rack") {
+            return Ok(None);
+        }
+        let (rack, result) = call(script, "rack", rack)?;
+        let order = result.into_array()
+            .map_err(|found| ScriptError::Rack(found.to_string()))?
+            .into_iter()
+            .map(|n| n.as_int().ok().and_then(|n| u8::try_from(n).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| ScriptError::Rack("array".to_string()))?;
+        Ok(Some((order, rack)))
+    }
+}
+
+// Sem o recurso "scripting" as variantes não carregam e as partidas salvas com
+// uma delas jogam só com as regras padrão
+#[cfg(not(feature = "scripting"))]
+mod engine {
+    use super::{ScriptError, ScriptRack, ScriptTurn};
+
+    #[derive(Debug)]
+    pub struct Compiled;
+
+    pub fn compile(_source: &str) -> Result<Compiled, ScriptError> {
+        Ok(Compiled)
+    }
+
+    pub fn check(_script: &Compiled) -> Result<(), ScriptError> {
+        Err(ScriptError::Disabled)
+    }
+
+    pub fn turn_end(_script: &Compiled, _turn: ScriptTurn) -> Result<ScriptTurn, ScriptError> {
+        Err(ScriptError::Disabled)
+    }
+
+    pub fn rack(_script: &Compiled, _rack: ScriptRack) -> Result<Option<(Vec<u8>, ScriptRack)>, ScriptError> {
+        Ok(None)
+    }
+}
+
+impl Game {
+    fn script_turn(&self) -> ScriptTurn {
+        let cue = self.cue_ball_idx.and_then(|idx| self.balls.get(idx));
+        ScriptTurn {
+            shooter: self.current_player.0,
+            is_break: self.is_break_shot,
+            potted: self.potted_ball_numbers_this_turn.clone(),
+            cue_potted: cue.is_none_or(|ball| ball.in_pocket),
+            first_contact: self.shot_log.first_contact(0),
+            rail_after_contact: self.shot_log.cushion_after_first_contact(0),
+            rail_balls: self.shot_log.distinct_balls_to_cushion(&[0]),
+            balls_on_table: self.balls.iter().filter(|ball| !ball.in_pocket && ball.number != 0).map(|ball| ball.number).collect(),
+            striped: self.ball_definitions_map.values().filter(|def| def.is_striped).map(|def| def.number).collect(),
+            groups: self.players.iter().map(|player| player.group).collect(),
+            scores: self.players.iter().map(|player| player.score).collect(),
+            fouls: self.players.iter().map(|player| player.consecutive_fouls).collect(),
+            eliminated: self.players.iter().map(|player| player.eliminated).collect(),
+            ..ScriptTurn::default()
+        }
+    }
+
+    // Ordem do triângulo pedida pelo script, ou None para o sorteio padrão. Bola
+    // repetida ou que não existe na modalidade invalida a ordem inteira.
+    pub(crate) fn scripted_rack_order(&mut self, rng: &mut RackRng) -> Option<Vec<u8>> {
+        let script = self.script.clone()?;
+        let mut balls: Vec<u8> = self.ball_definitions_map.keys().copied().filter(|&n| n != 0).collect();
+        balls.sort_unstable();
+        let striped = self.ball_definitions_map.values().filter(|def| def.is_striped).map(|def| def.number).collect();
+        let rack = ScriptRack { balls: balls.clone(), striped, rng: rng.clone() };
+        match script.compiled().and_then(|compiled| engine::rack(compiled, rack)) {
+            Ok(Some((order, rack))) => {
+                let valid = order.len() <= 15
+                    && order.iter().all(|n| balls.contains(n))
+                    && order.iter().enumerate().all(|(i, n)| !order[..i].contains(n));
+                if !valid {
+                    self.notify(Notification::ScriptError(ScriptError::Rack(format!("{:?}", order))));
+                    return None;
+                }
+                *rng = rack.rng;
+                Some(order)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                self.notify(Notification::ScriptError(e));
+                None
+            }
+        }
+    }
+
+    // Fim da jogada numa variante com script. Erro no script conta como tacada sem
+    // decisão nenhuma: aparece na tela e a vez passa.
+    pub(crate) fn handle_scripted_turn_end(&mut self) {
+        let turn = self.script_turn();
+        let cue_potted = turn.cue_potted;
+        let decided = match self.script.as_ref().map(|script| script.compiled().and_then(|compiled| engine::turn_end(compiled, turn.clone()))) {
+            Some(Ok(decided)) => decided,
+            Some(Err(e)) => {
+                self.notify(Notification::ScriptError(e));
+                turn
+            }
+            None => turn,
+        };
+        let shooter = self.current_player;
+        self.is_break_shot = false;
+
+        for &(idx, group) in &decided.new_groups {
+            self.players[idx].group = group;
+            self.notify(Notification::GroupAssigned { player: PlayerId(idx), group });
+        }
+        for &(idx, points) in &decided.points {
+            self.players[idx].score += points;
+            let player = PlayerId(idx);
+            self.notify(if points >= 0 {
+                Notification::Scored { player, points }
+            } else {
+                Notification::PointsLost { player, points: -points }
+            });
+        }
+        for &number in &decided.respots {
+            self.spot_ball_number(number);
+        }
+
+        self.shot_foul = decided.foul;
+        self.track_foul(shooter, decided.foul.is_some());
+        if let Some(reason) = decided.foul {
+            self.notify(Notification::Foul { player: shooter, reason });
+        }
+
+        if let Some(winner) = decided.winner {
+            self.notify(Notification::Won { player: PlayerId(winner) });
+            self.end_rack(PlayerId(winner));
+            return;
+        }
+        for &idx in &decided.losers {
+            if self.eliminate(PlayerId(idx), LossReason::Rules) {
+                return;
+            }
+        }
+
+        if cue_potted || decided.ball_in_hand {
+            self.ball_in_hand_area = if decided.ball_in_hand { PlacementArea::Anywhere } else { PlacementArea::Kitchen };
+            self.set_state(GameState::RepositionCueBall);
+        }
+        let keep_turn = decided.keep_turn && decided.foul.is_none() && !cue_potted && !self.current().eliminated;
+        if !keep_turn {
+            self.advance_turn();
+        }
+        if self.game_state == GameState::RepositionCueBall {
+            self.notify_ball_in_hand();
+        } else {
+            self.set_state(GameState::Aiming);
+            self.notify(if keep_turn {
+                Notification::PlaysAgain { player: self.current_player }
+            } else {
+                Notification::TurnChanged { player: self.current_player }
+            });
+        }
+    }
+}
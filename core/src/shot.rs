@@ -0,0 +1,90 @@
+// Registro do que aconteceu durante uma tacada, usado pelas regras no fim da jogada
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShotEvent {
+    BallContact(u8, u8),
+    Cushion(u8),
+    Pocketed(u8, usize), // bola e índice da caçapa
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShotLog {
+    pub events: Vec<ShotEvent>,
+}
+
+impl ShotLog {
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn record(&mut self, event: ShotEvent) {
+        self.events.push(event);
+    }
+
+    fn first_contact_index(&self, striker: u8) -> Option<usize> {
+        self.events.iter().position(|event| {
+            matches!(*event, ShotEvent::BallContact(a, b) if a == striker || b == striker)
+        })
+    }
+
+    // Primeira bola tocada pela bola `striker` (normalmente a branca)
+    pub fn first_contact(&self, striker: u8) -> Option<u8> {
+        let idx = self.first_contact_index(striker)?;
+        match self.events[idx] {
+            ShotEvent::BallContact(a, b) => Some(if a == striker { b } else { a }),
+            _ => None,
+        }
+    }
+
+    // Alguma bola tocou a tabela depois do primeiro contato da `striker`
+    pub fn cushion_after_first_contact(&self, striker: u8) -> bool {
+        self.first_contact_index(striker).is_some_and(|idx| {
+            self.events[idx..].iter().any(|event| matches!(event, ShotEvent::Cushion(_)))
+        })
+    }
+
+    // Tabelas que a `striker` tocou antes de atingir a segunda bola diferente,
+    // ou None se ela não chegou a tocar duas bolas (carambola)
+    pub fn cushions_before_second_contact(&self, striker: u8) -> Option<usize> {
+        let mut cushions = 0;
+        let mut first_ball: Option<u8> = None;
+        for event in &self.events {
+            match *event {
+                ShotEvent::Cushion(number) if number == striker => cushions += 1,
+                ShotEvent::BallContact(a, b) if a == striker || b == striker => {
+                    let other = if a == striker { b } else { a };
+                    match first_ball {
+                        None => first_ball = Some(other),
+                        Some(first) if first != other => return Some(cushions),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Caçapa em que a bola caiu nesta tacada
+    pub fn pocket_of(&self, number: u8) -> Option<usize> {
+        self.events.iter().find_map(|event| match *event {
+            ShotEvent::Pocketed(ball, pocket) if ball == number => Some(pocket),
+            _ => None,
+        })
+    }
+
+    // Quantas bolas diferentes tocaram a tabela, ignorando as de `excluding`
+    pub fn distinct_balls_to_cushion(&self, excluding: &[u8]) -> usize {
+        let mut seen: Vec<u8> = Vec::new();
+        for event in &self.events {
+            if let ShotEvent::Cushion(number) = *event
+                && !excluding.contains(&number)
+                && !seen.contains(&number)
+            {
+                seen.push(number);
+            }
+        }
+        seen.len()
+    }
+}
@@ -0,0 +1,419 @@
+use glam::{Vec2, vec2};
+use crate::Game;
+use crate::ball::Ball;
+use crate::colors::ball_colors;
+use crate::config::{
+    is_snooker_red, snooker_ball_value, SNOOKER_COLOURS, SNOOKER_YELLOW, SNOOKER_GREEN, SNOOKER_BROWN,
+    SNOOKER_BLUE, SNOOKER_PINK, SNOOKER_BLACK,
+};
+use crate::constants::SNOOKER_MIN_FOUL_VALUE;
+use crate::types::{Foul, GameState, PlayerId, SnookerPhase};
+use crate::notification::Notification;
+
+impl Game {
+    pub fn snooker_d_radius(&self) -> f32 {
+        self.table.d_radius * self.table_scale()
+    }
+
+    // Ponto de cada cor na mesa (a validação da mesa de snooker garante todos)
+    pub fn snooker_spot(&self, colour: u8) -> Vec2 {
+        let name = match colour {
+            SNOOKER_YELLOW => "yellow",
+            SNOOKER_GREEN => "green",
+            SNOOKER_BROWN => "brown",
+            SNOOKER_BLUE => "blue",
+            SNOOKER_PINK => "pink",
+            _ => "black",
+        };
+        self.table_spot(name).unwrap_or(vec2(self.table_width / 2.0, self.table_height / 2.0))
+    }
+
+    pub fn setup_snooker_balls(&mut self) {
+        self.balls.clear();
+        let r = self.ball_radius;
+        let d_radius = self.snooker_d_radius();
+        let cue_pos = self.head_spot() - vec2(d_radius * 0.3, d_radius * 0.5);
+        self.balls.push(Ball::new(cue_pos.x, cue_pos.y, ball_colors::CUE, 0, false, r));
+        self.cue_ball_idx = Some(0);
+
+        // Triângulo das vermelhas logo atrás da rosa, sem encostar nela
+        let apex = self.snooker_spot(SNOOKER_PINK) + vec2(r * 2.0 + 1.0, 0.0);
+        let mut number = 1;
+        for row in 0..5 {
+            for col in 0..=row {
+                let x = apex.x + row as f32 * (r * 2.0 * 0.8660254);
+                let y = apex.y + col as f32 * r * 2.0 - row as f32 * r;
+                let def = &self.ball_definitions_map[&number];
+                self.balls.push(Ball::from_definition(vec2(x, y), def, r));
+                number += 1;
+            }
+        }
+
+        for colour in SNOOKER_COLOURS {
+            let def = &self.ball_definitions_map[&colour];
+            self.balls.push(Ball::from_definition(self.snooker_spot(colour), def, r));
+        }
+    }
+
+    fn reds_on_table(&self) -> usize {
+        self.balls.iter().filter(|ball| !ball.in_pocket && is_snooker_red(ball.number)).count()
+    }
+
+    fn snooker_ball_on_table(&self, number: u8) -> bool {
+        self.balls.iter().any(|ball| ball.number == number && !ball.in_pocket)
+    }
+
+    // Bola da vez no início de uma nova visita à mesa
+    pub fn snooker_phase_for_new_visit(&self) -> SnookerPhase {
+        if self.reds_on_table() > 0 {
+            return SnookerPhase::Red;
+        }
+        let lowest_colour = SNOOKER_COLOURS.iter()
+            .copied()
+            .find(|&colour| self.snooker_ball_on_table(colour))
+            .unwrap_or(SNOOKER_BLACK);
+        SnookerPhase::Clearance(lowest_colour)
+    }
+
+    pub fn snooker_points_remaining(&self) -> i32 {
+        let reds = self.reds_on_table() as i32;
+        let colours: i32 = SNOOKER_COLOURS.iter()
+            .filter(|&&colour| self.snooker_ball_on_table(colour))
+            .map(|&colour| snooker_ball_value(colour))
+            .sum();
+        let pending_colour = if self.snooker_phase == SnookerPhase::Colour { 7 } else { 0 };
+        reds * 8 + colours + pending_colour
+    }
+
+    // Bolas que podem ser cantadas no HUD: cores depois de uma vermelha, ou
+    // qualquer bola que não seja a da vez quando há bola livre
+    pub fn snooker_callable_balls(&self) -> Vec<u8> {
+        let colours_on_table = SNOOKER_COLOURS.iter().copied().filter(|&colour| self.snooker_ball_on_table(colour));
+        match self.snooker_phase {
+            SnookerPhase::Colour => colours_on_table.collect(),
+            SnookerPhase::Red if self.free_ball => colours_on_table.collect(),
+            SnookerPhase::Clearance(on) if self.free_ball => colours_on_table.filter(|&colour| colour != on).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn snooker_balls_on(&self, free_ball: Option<u8>) -> Vec<u8> {
+        let mut on: Vec<u8> = match self.snooker_phase {
+            SnookerPhase::Red => (1..=15).collect(),
+            SnookerPhase::Colour => self.called_ball.into_iter().collect(),
+            SnookerPhase::Clearance(colour) => vec![colour],
+        };
+        on.extend(free_ball);
+        on
+    }
+
+    fn snooker_value_of_ball_on(&self) -> i32 {
+        match self.snooker_phase {
+            SnookerPhase::Red => 1,
+            SnookerPhase::Colour => self.called_ball.map_or(0, snooker_ball_value),
+            SnookerPhase::Clearance(colour) => snooker_ball_value(colour),
+        }
+    }
+
+    // Valor mínimo de uma falta: 4 pontos, ou o valor da bola da vez se for maior
+    pub(crate) fn snooker_foul_value(&self) -> i32 {
+        SNOOKER_MIN_FOUL_VALUE.max(self.snooker_value_of_ball_on())
+    }
+
+    fn snooker_foul(&self, on: &[u8], cue_ball_is_pocketed: bool) -> Option<(Foul, i32)> {
+        let mut foul = None;
+        let mut value = self.snooker_foul_value();
+
+        match self.shot_log.first_contact(0) {
+            None => foul = Some(Foul::NoContact),
+            Some(number) if !on.contains(&number) => {
+                foul = Some(Foul::WrongBallFirst);
+                value = value.max(snooker_ball_value(number));
+            }
+            _ => {}
+        }
+        for &number in &self.potted_ball_numbers_this_turn {
+            if !on.contains(&number) {
+                foul = foul.or(Some(Foul::WrongBallPotted));
+                value = value.max(snooker_ball_value(number));
+            }
+        }
+        if cue_ball_is_pocketed {
+            foul = foul.or(Some(Foul::Scratch));
+        }
+        foul.map(|foul| (foul, value))
+    }
+
+    pub fn handle_snooker_turn_end(&mut self) {
+        let cue_ball_is_pocketed = self.cue_ball_idx.is_none_or(|idx| self.balls[idx].in_pocket);
+        let shooter = self.current_player;
+        self.is_break_shot = false;
+
+        let free_ball = if self.free_ball { self.called_ball } else { None };
+        self.free_ball = false;
+        // Sem cor cantada, a primeira cor tocada vale como cantada
+        if self.snooker_phase == SnookerPhase::Colour && self.called_ball.is_none() {
+            self.called_ball = self.shot_log.first_contact(0).filter(|number| SNOOKER_COLOURS.contains(number));
+        }
+        let on = self.snooker_balls_on(free_ball);
+        let potted = self.potted_ball_numbers_this_turn.clone();
+
+        if let Some((foul, value)) = self.snooker_foul(&on, cue_ball_is_pocketed) {
+            for &number in potted.iter().filter(|&&number| !is_snooker_red(number)) {
+                self.respot_colour(number);
+            }
+            self.apply_snooker_foul(foul, value, cue_ball_is_pocketed);
+            return;
+        }
+
+        let mut points = 0;
+        match self.snooker_phase {
+            SnookerPhase::Red => {
+                points = potted.len() as i32;
+                if let Some(number) = free_ball.filter(|number| potted.contains(number)) {
+                    self.respot_colour(number);
+                }
+                if points > 0 {
+                    self.snooker_phase = SnookerPhase::Colour;
+                }
+            }
+            SnookerPhase::Colour => {
+                if let Some(colour) = self.called_ball.filter(|colour| potted.contains(colour)) {
+                    points = snooker_ball_value(colour);
+                    self.respot_colour(colour);
+                    self.snooker_phase = if self.reds_on_table() > 0 {
+                        SnookerPhase::Red
+                    } else {
+                        SnookerPhase::Clearance(SNOOKER_YELLOW)
+                    };
+                }
+            }
+            SnookerPhase::Clearance(colour) => {
+                if potted.contains(&colour) {
+                    points = snooker_ball_value(colour);
+                    if colour == SNOOKER_BLACK {
+                        self.player_mut(shooter).score += points;
+                        self.notify(Notification::Scored { player: shooter, points });
+                        self.end_snooker_frame();
+                        return;
+                    } else {
+                        self.snooker_phase = SnookerPhase::Clearance(colour + 1);
+                    }
+                } else if let Some(number) = free_ball.filter(|number| potted.contains(number)) {
+                    points = snooker_ball_value(colour);
+                    self.respot_colour(number);
+                }
+            }
+        }
+        self.called_ball = None;
+
+        if points > 0 {
+            self.player_mut(shooter).score += points;
+            self.notify(Notification::Scored { player: shooter, points });
+            self.notify(Notification::PlaysAgain { player: shooter });
+        } else {
+            self.advance_turn();
+            self.snooker_phase = self.snooker_phase_for_new_visit();
+            self.notify(Notification::TurnChanged { player: self.current_player });
+        }
+        self.set_state(GameState::Aiming);
+    }
+
+    // Falta no snooker, na mesa ou no relógio: os pontos vão para o adversário, que
+    // assume a mesa com a branca na mão se `cue_ball_in_hand` e com bola livre se
+    // ficou sem ver a bola da vez. Falta na preta final encerra o frame.
+    pub(crate) fn apply_snooker_foul(&mut self, foul: Foul, value: i32, cue_ball_in_hand: bool) {
+        let shooter = self.current_player;
+        let opponent = self.next_player();
+        self.shot_foul = Some(foul);
+        self.player_mut(opponent).score += value;
+        self.called_ball = None;
+        self.notify(Notification::Foul { player: shooter, reason: foul });
+        self.notify(Notification::PointsAwarded { player: opponent, points: value });
+
+        if matches!(self.snooker_phase, SnookerPhase::Clearance(SNOOKER_BLACK)) {
+            self.end_snooker_frame();
+            return;
+        }
+
+        self.current_player = opponent;
+        self.snooker_phase = self.snooker_phase_for_new_visit();
+        if cue_ball_in_hand {
+            self.set_state(GameState::RepositionCueBall);
+            self.notify_ball_in_hand();
+        } else {
+            self.set_state(GameState::Aiming);
+            self.notify(Notification::TurnChanged { player: self.current_player });
+            if self.is_snookered() {
+                self.free_ball = true;
+                self.notify(Notification::FreeBall { player: self.current_player });
+            }
+        }
+    }
+
+    // Encerra o frame depois da preta final. Com empate a preta volta ao ponto
+    // e o adversário joga com a branca no D.
+    fn end_snooker_frame(&mut self) {
+        let (first, second) = (self.players[0].score, self.players[1].score);
+        if first == second {
+            self.respot_colour(SNOOKER_BLACK);
+            self.snooker_phase = SnookerPhase::Clearance(SNOOKER_BLACK);
+            self.advance_turn();
+            self.set_state(GameState::RepositionCueBall);
+            self.notify(Notification::SnookerTie);
+            self.notify_ball_in_hand();
+            return;
+        }
+        let winner = if first > second { PlayerId(0) } else { PlayerId(1) };
+        self.notify(Notification::FrameWon { player: winner, high: first.max(second), low: first.min(second) });
+        self.end_rack(winner);
+    }
+
+    // Recoloca uma cor no seu ponto. Se estiver ocupado usa o ponto livre de maior
+    // valor e, sem nenhum livre, a posição mais próxima do próprio ponto.
+    pub fn respot_colour(&mut self, colour: u8) {
+        let Some(ball_idx) = self.balls.iter().position(|ball| ball.number == colour) else { return; };
+        let own_spot = self.snooker_spot(colour);
+
+        let spot = std::iter::once(own_spot)
+            .chain(SNOOKER_COLOURS.iter().rev().map(|&other| self.snooker_spot(other)))
+            .find(|&spot| self.is_spot_free(spot, ball_idx))
+            .or_else(|| self.nearest_free_position(own_spot, ball_idx))
+            .unwrap_or(own_spot);
+        self.place_ball(ball_idx, spot);
+    }
+
+    fn nearest_free_position(&self, from: Vec2, ball_idx: usize) -> Option<Vec2> {
+        let step = self.ball_radius * 0.25;
+        let max_steps = (self.table_width / step) as i32;
+        (1..max_steps)
+            .flat_map(|i| [from + vec2(step * i as f32, 0.0), from - vec2(step * i as f32, 0.0)])
+            .filter(|pos| pos.x > self.ball_radius && pos.x < self.table_width - self.ball_radius)
+            .find(|&pos| self.is_spot_free(pos, ball_idx))
+    }
+
+    // A branca está "snookered" quando não consegue acertar em linha reta as duas
+    // extremidades de nenhuma bola da vez
+    fn is_snookered(&self) -> bool {
+        let Some(cue_ball) = self.cue_ball_idx.map(|idx| &self.balls[idx]) else { return false; };
+        let targets: Vec<&Ball> = self.balls.iter()
+            .filter(|ball| !ball.in_pocket && match self.snooker_phase {
+                SnookerPhase::Red => is_snooker_red(ball.number),
+                SnookerPhase::Colour => SNOOKER_COLOURS.contains(&ball.number),
+                SnookerPhase::Clearance(colour) => ball.number == colour,
+            })
+            .collect();
+        if targets.is_empty() { return false; }
+
+        !targets.iter().any(|target| {
+            let dir = (target.pos - cue_ball.pos).normalize_or_zero();
+            let perp = vec2(-dir.y, dir.x) * (self.ball_radius * 2.0 * 0.99);
+            self.is_path_clear(cue_ball.pos, target.pos + perp, target.number)
+                && self.is_path_clear(cue_ball.pos, target.pos - perp, target.number)
+        })
+    }
+
+    fn is_path_clear(&self, from: Vec2, to: Vec2, target: u8) -> bool {
+        let segment = to - from;
+        let length_sq = segment.length_squared().max(0.001);
+        let min_dist_sq = (self.ball_radius * 2.0).powi(2);
+        self.balls.iter()
+            .filter(|ball| !ball.in_pocket && ball.number != 0 && ball.number != target)
+            .all(|ball| {
+                let t = ((ball.pos - from).dot(segment) / length_sq).clamp(0.0, 1.0);
+                (from + segment * t).distance_squared(ball.pos) >= min_dist_sq
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Game;
+    use crate::config::{SNOOKER_BLACK, SNOOKER_BLUE, SNOOKER_BROWN, SNOOKER_GREEN, SNOOKER_PINK};
+    use crate::notification::Notification;
+    use crate::shot::ShotEvent;
+    use crate::types::{Foul, GameState, GameVariant, PlayerId, SnookerPhase};
+
+    fn snooker() -> Game {
+        let mut game = Game::new();
+        game.variant = GameVariant::Snooker;
+        game.new_match();
+        game.game_state = GameState::BallsMoving;
+        game.is_break_shot = false;
+        game.take_notifications();
+        game
+    }
+
+    fn pot(game: &mut Game, number: u8) {
+        let idx = game.balls.iter().position(|ball| ball.number == number).unwrap();
+        game.balls[idx].in_pocket = true;
+        game.potted_ball_numbers_this_turn.push(number);
+        game.shot_log.record(ShotEvent::Pocketed(number, 0));
+    }
+
+    // Pontos que o adversário ganhou pela falta
+    fn awarded(game: &Game, foul: Foul, points: i32) {
+        assert_eq!(game.shot_foul, Some(foul));
+        assert_eq!(game.players[1].score, points);
+        assert!(game.notifications.contains(&Notification::PointsAwarded { player: PlayerId(1), points }));
+    }
+
+    #[test]
+    fn foul_is_worth_at_least_four() {
+        let mut game = snooker();
+        game.handle_snooker_turn_end();
+        awarded(&game, Foul::NoContact, 4);
+        assert_eq!(game.current_player, PlayerId(1));
+    }
+
+    #[test]
+    fn hitting_a_colour_first_on_the_reds_costs_its_value() {
+        let mut game = snooker();
+        game.shot_log.record(ShotEvent::BallContact(0, SNOOKER_PINK));
+        game.handle_snooker_turn_end();
+        awarded(&game, Foul::WrongBallFirst, 6);
+    }
+
+    #[test]
+    fn potting_the_black_off_a_red_costs_seven_and_respots_it() {
+        let mut game = snooker();
+        game.shot_log.record(ShotEvent::BallContact(0, 1));
+        pot(&mut game, 1);
+        pot(&mut game, SNOOKER_BLACK);
+        game.handle_snooker_turn_end();
+        awarded(&game, Foul::WrongBallPotted, 7);
+        assert!(game.balls.iter().any(|ball| ball.number == SNOOKER_BLACK && !ball.in_pocket));
+    }
+
+    #[test]
+    fn foul_on_a_called_colour_is_worth_that_colour() {
+        let mut game = snooker();
+        game.snooker_phase = SnookerPhase::Colour;
+        game.called_ball = Some(SNOOKER_BLUE);
+        game.shot_log.record(ShotEvent::BallContact(0, SNOOKER_GREEN));
+        game.handle_snooker_turn_end();
+        awarded(&game, Foul::WrongBallFirst, 5);
+    }
+
+    #[test]
+    fn scratch_in_the_clearance_gives_ball_in_hand() {
+        let mut game = snooker();
+        game.snooker_phase = SnookerPhase::Clearance(SNOOKER_BROWN);
+        game.shot_log.record(ShotEvent::BallContact(0, SNOOKER_BROWN));
+        let cue_idx = game.cue_ball_idx.unwrap();
+        game.balls[cue_idx].in_pocket = true;
+        game.handle_snooker_turn_end();
+        awarded(&game, Foul::Scratch, 4);
+        assert_eq!(game.game_state, GameState::RepositionCueBall);
+    }
+
+    #[test]
+    fn foul_value_follows_the_ball_on() {
+        let mut game = snooker();
+        assert_eq!(game.snooker_foul_value(), 4);
+        game.snooker_phase = SnookerPhase::Clearance(SNOOKER_PINK);
+        assert_eq!(game.snooker_foul_value(), 6);
+        game.snooker_phase = SnookerPhase::Clearance(SNOOKER_BLACK);
+        assert_eq!(game.snooker_foul_value(), 7);
+    }
+}
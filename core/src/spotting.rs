@@ -0,0 +1,55 @@
+use glam::{Vec2, vec2};
+use crate::Game;
+
+impl Game {
+    // Posição livre quando nenhuma outra bola na mesa encosta nela
+    pub fn is_spot_free(&self, pos: Vec2, ball_idx: usize) -> bool {
+        let min_dist_sq = (self.ball_radius * 2.0).powi(2);
+        self.balls.iter().enumerate().all(|(i, ball)| {
+            i == ball_idx || ball.in_pocket || ball.pos.distance_squared(pos) >= min_dist_sq
+        })
+    }
+
+    // Recoloca uma bola no foot spot. Ocupado o ponto, ela vai o mais perto possível
+    // atrás dele na linha longa (em direção à tabela de fundo) e, sem espaço ali,
+    // à frente dele.
+    pub fn spot_ball(&mut self, ball_idx: usize) {
+        let foot_spot = self.foot_spot();
+        let step = self.ball_radius * 0.25;
+        let max_steps = (self.table_width / step) as i32;
+        let behind = (1..max_steps).map(|i| foot_spot + vec2(step * i as f32, 0.0));
+        let in_front = (1..max_steps).map(|i| foot_spot - vec2(step * i as f32, 0.0));
+
+        let spot = std::iter::once(foot_spot)
+            .chain(behind.take_while(|pos| pos.x < self.table_width - self.ball_radius))
+            .chain(in_front.take_while(|pos| pos.x > self.ball_radius))
+            .find(|&pos| self.is_spot_free(pos, ball_idx))
+            .unwrap_or(foot_spot);
+        self.place_ball(ball_idx, spot);
+    }
+
+    pub fn spot_ball_number(&mut self, number: u8) {
+        if let Some(idx) = self.balls.iter().position(|ball| ball.number == number) {
+            self.spot_ball(idx);
+        }
+    }
+
+    // Bolas que pararam fora da mesa: as numeradas voltam ao foot spot e a branca
+    // conta como encaçapada
+    pub fn spot_balls_off_table(&mut self) {
+        for idx in 0..self.balls.len() {
+            let ball = &self.balls[idx];
+            let on_table = ball.pos.x >= 0.0 && ball.pos.x <= self.table_width
+                && ball.pos.y >= 0.0 && ball.pos.y <= self.table_height;
+            if ball.in_pocket || on_table {
+                continue;
+            }
+            if Some(idx) == self.cue_ball_idx {
+                self.balls[idx].in_pocket = true;
+                self.balls[idx].vel = Vec2::ZERO;
+            } else {
+                self.spot_ball(idx);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use glam::Vec2;
+use crate::Game;
+use crate::constants::MAX_STATE_TRANSITIONS;
+use crate::types::{GameState, PlayerGroup};
+use crate::notification::Notification;
+
+// Comandos do jogador. Tudo que a interface (ou o simulador, ou o replay) pede ao
+// jogo passa por Game::execute, que recusa o que não vale no estado atual.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameCommand {
+    PlaceCueBall(Vec2),
+    CallBall(u8),
+    CallPocket(usize),
+    Shoot { angle: f32, power: f32 },
+    Rerack,
+    AcceptTable,
+    ChooseGroup(PlayerGroup),
+    UseExtension,
+    NextRack,
+    NewMatch,
+}
+
+impl GameCommand {
+    pub fn allowed_in(&self, state: GameState) -> bool {
+        match self {
+            GameCommand::PlaceCueBall(_) => state == GameState::RepositionCueBall,
+            GameCommand::CallBall(_) | GameCommand::CallPocket(_) | GameCommand::Shoot { .. } => state == GameState::Aiming,
+            GameCommand::UseExtension => matches!(state, GameState::Aiming | GameState::RepositionCueBall),
+            GameCommand::Rerack | GameCommand::AcceptTable => state == GameState::BreakChoice,
+            GameCommand::ChooseGroup(_) => state == GameState::GroupChoice,
+            GameCommand::NextRack => state == GameState::GameOver,
+            GameCommand::NewMatch => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommandError {
+    // O comando não existe no estado atual (tacada com bolas em movimento, por exemplo)
+    InvalidState { command: GameCommand, state: GameState },
+    // O estado é o certo, mas o jogo não aceitou (branca fora da área, força baixa...)
+    Refused(GameCommand),
+}
+
+// Uma mudança de estado, na ordem em que aconteceram
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateTransition {
+    pub from: GameState,
+    pub to: GameState,
+}
+
+impl GameState {
+    // Transições permitidas. Uma tacada sempre passa por Shooting e BallsMoving
+    // antes de as regras decidirem a vez.
+    pub fn can_transition_to(self, to: GameState) -> bool {
+        use GameState::*;
+        match (self, to) {
+            // Nova partida ou novo rack, de qualquer ponto
+            (_, Initializing) => true,
+            (Initializing, Aiming) => true,
+            (Aiming, Shooting) => true,
+            (Shooting, BallsMoving) => true,
+            (BallsMoving, Aiming | RepositionCueBall | BreakChoice | GroupChoice | GameOver | MatchOver) => true,
+            (RepositionCueBall, Aiming) => true,
+            (BreakChoice, Aiming | RepositionCueBall) => true,
+            (GroupChoice, Aiming) => true,
+            // O relógio pode encerrar o rack enquanto o jogador decide, ou dar bola na mão
+            (Aiming | RepositionCueBall, GameOver | MatchOver) => true,
+            (Aiming, RepositionCueBall) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Game {
+    pub fn execute(&mut self, command: GameCommand) -> Result<(), CommandError> {
+        if !command.allowed_in(self.game_state) {
+            return Err(CommandError::InvalidState { command, state: self.game_state });
+        }
+        let accepted = match command {
+            GameCommand::PlaceCueBall(pos) => self.place_cue_ball(pos),
+            GameCommand::CallBall(number) => self.call_ball(number),
+            GameCommand::CallPocket(pocket_idx) => self.call_pocket(pocket_idx),
+            GameCommand::Shoot { angle, power } => self.shoot(angle, power),
+            GameCommand::Rerack => {
+                self.rerack_after_illegal_break();
+                true
+            }
+            GameCommand::AcceptTable => {
+                self.accept_table_after_illegal_break();
+                true
+            }
+            GameCommand::ChooseGroup(group) => {
+                self.choose_group_after_break(group);
+                true
+            }
+            GameCommand::UseExtension => self.use_extension(),
+            GameCommand::NextRack => {
+                self.start_next_rack();
+                true
+            }
+            GameCommand::NewMatch => {
+                self.new_match();
+                true
+            }
+        };
+        if accepted { Ok(()) } else { Err(CommandError::Refused(command)) }
+    }
+
+    pub fn game_state(&self) -> GameState {
+        self.game_state
+    }
+
+    // Único jeito de mudar game_state. Transição fora da tabela é erro de regra:
+    // o estado fica como está e o erro sai como aviso InvalidTransition (em debug o
+    // jogo para ali mesmo).
+    pub(crate) fn set_state(&mut self, to: GameState) {
+        let from = self.game_state;
+        if from == to { return; }
+        debug_assert!(from.can_transition_to(to), "transição inválida: {:?} -> {:?}", from, to);
+        if !from.can_transition_to(to) {
+            self.notify(Notification::InvalidTransition { from, to });
+            return;
+        }
+        self.game_state = to;
+        if self.state_history.len() >= MAX_STATE_TRANSITIONS {
+            self.state_history.remove(0);
+        }
+        self.state_history.push(StateTransition { from, to });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandError, GameCommand, StateTransition};
+    use crate::Game;
+    use crate::types::GameState;
+
+    #[test]
+    fn a_shot_goes_through_shooting_and_balls_moving() {
+        use GameState::*;
+        assert!(Aiming.can_transition_to(Shooting));
+        assert!(Shooting.can_transition_to(BallsMoving));
+        assert!(BallsMoving.can_transition_to(Aiming));
+        assert!(BallsMoving.can_transition_to(RepositionCueBall));
+        assert!(!Aiming.can_transition_to(BallsMoving));
+        assert!(!Shooting.can_transition_to(Aiming));
+    }
+
+    #[test]
+    fn finished_rack_only_leaves_through_a_new_rack() {
+        use GameState::*;
+        for state in [Aiming, Shooting, BallsMoving, RepositionCueBall, BreakChoice, GroupChoice] {
+            assert!(!GameOver.can_transition_to(state), "GameOver -> {:?}", state);
+            assert!(!MatchOver.can_transition_to(state), "MatchOver -> {:?}", state);
+        }
+        assert!(GameOver.can_transition_to(Initializing));
+        assert!(MatchOver.can_transition_to(Initializing));
+    }
+
+    #[test]
+    fn clock_can_end_the_rack_while_the_player_decides() {
+        use GameState::*;
+        assert!(Aiming.can_transition_to(GameOver));
+        assert!(RepositionCueBall.can_transition_to(MatchOver));
+        assert!(Aiming.can_transition_to(RepositionCueBall));
+        assert!(!Shooting.can_transition_to(GameOver));
+    }
+
+    #[test]
+    fn set_state_records_the_transition() {
+        let mut game = Game::new();
+        game.state_history.clear();
+        game.set_state(GameState::Shooting);
+        assert_eq!(game.game_state(), GameState::Shooting);
+        assert_eq!(game.state_history, vec![StateTransition { from: GameState::Aiming, to: GameState::Shooting }]);
+    }
+
+    #[test]
+    fn command_outside_its_state_is_refused() {
+        let mut game = Game::new();
+        game.game_state = GameState::BallsMoving;
+        let shot = GameCommand::Shoot { angle: 0.0, power: 0.5 };
+        assert_eq!(game.execute(shot), Err(CommandError::InvalidState { command: shot, state: GameState::BallsMoving }));
+        assert_eq!(game.execute(GameCommand::NextRack), Err(CommandError::InvalidState { command: GameCommand::NextRack, state: GameState::BallsMoving }));
+    }
+}
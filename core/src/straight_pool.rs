@@ -0,0 +1,318 @@
+use glam::Vec2;
+use crate::Game;
+use crate::constants::{
+    CONSECUTIVE_FOUL_LIMIT, STRAIGHT_POOL_FOUL_PENALTY, STRAIGHT_POOL_BREAK_FOUL_PENALTY, STRAIGHT_POOL_THREE_FOUL_PENALTY,
+};
+use crate::types::{Foul, GameState};
+use crate::notification::Notification;
+
+impl Game {
+    // Contínuo (14.1): cada bola cantada vale um ponto, e quando sobra uma bola
+    // as outras quatorze voltam para o triângulo.
+    pub fn handle_straight_pool_turn_end(&mut self) {
+        let cue_ball_is_pocketed = self.cue_ball_idx.is_none_or(|idx| self.balls[idx].in_pocket);
+        let shooter = self.current_player;
+        let was_break_shot = self.is_break_shot;
+        self.is_break_shot = false;
+
+        let called_ball_potted = self.called_ball
+            .is_some_and(|number| self.potted_ball_numbers_this_turn.contains(&number));
+        let mut player_continues_turn = false;
+
+        if let Some(foul) = self.straight_pool_foul(was_break_shot, called_ball_potted, cue_ball_is_pocketed) {
+            self.shot_foul = Some(foul);
+            let penalty = if foul == Foul::IllegalBreak {
+                STRAIGHT_POOL_BREAK_FOUL_PENALTY
+            } else {
+                STRAIGHT_POOL_FOUL_PENALTY
+            };
+            self.notify(Notification::Foul { player: shooter, reason: foul });
+            if self.apply_straight_pool_foul(penalty) {
+                return;
+            }
+        } else {
+            self.current_mut().consecutive_fouls = 0;
+            if called_ball_potted {
+                // Como no 14.1, com a cantada dentro toda bola encaçapada na tacada vale ponto
+                let points = self.potted_ball_numbers_this_turn.len() as i32;
+                self.current_mut().score += points;
+                self.notify(Notification::Scored { player: shooter, points });
+                player_continues_turn = true;
+            } else if was_break_shot {
+                self.notify(Notification::LegalBreak);
+            } else {
+                self.notify(Notification::NoPoints);
+            }
+        }
+        self.called_ball = None;
+
+        let shooter_score = self.current().score;
+        if shooter_score >= self.target_score {
+            self.notify(Notification::Won { player: shooter });
+            self.end_rack(shooter);
+            return;
+        }
+
+        let mut cue_ball_in_hand = cue_ball_is_pocketed;
+        if player_continues_turn && self.object_balls_on_table() <= 1 {
+            cue_ball_in_hand |= self.rerack_straight_pool();
+            self.notify(Notification::NewRack);
+        }
+
+        if !player_continues_turn {
+            self.advance_turn();
+        }
+
+        if cue_ball_in_hand {
+            self.set_state(GameState::RepositionCueBall);
+            self.notify_ball_in_hand();
+        } else {
+            self.set_state(GameState::Aiming);
+            self.notify(if player_continues_turn {
+                Notification::PlaysAgain { player: shooter }
+            } else {
+                Notification::TurnChanged { player: self.current_player }
+            });
+        }
+    }
+
+    // Desconta a falta do jogador da vez; a terceira seguida soma a pena extra e o
+    // infrator sai de novo com as 15 bolas. Vale para falta na mesa e no relógio.
+    // Retorna true na terceira falta, com o rack já armado.
+    pub(crate) fn apply_straight_pool_foul(&mut self, mut penalty: i32) -> bool {
+        let shooter = self.current_player;
+        self.current_mut().consecutive_fouls += 1;
+        let third_foul = self.current().consecutive_fouls >= CONSECUTIVE_FOUL_LIMIT;
+        if third_foul {
+            penalty += STRAIGHT_POOL_THREE_FOUL_PENALTY;
+            self.current_mut().consecutive_fouls = 0;
+        }
+        self.current_mut().score -= penalty;
+        self.called_ball = None;
+        self.notify(Notification::PointsLost { player: shooter, points: penalty });
+
+        if third_foul {
+            self.rerack_straight_pool_full();
+            self.is_break_shot = true;
+            self.set_state(GameState::RepositionCueBall);
+            self.notify(Notification::ThirdFoul { player: shooter });
+            self.notify(Notification::Reracked { breaker: shooter });
+        }
+        third_foul
+    }
+
+    fn straight_pool_foul(&self, was_break_shot: bool, called_ball_potted: bool, cue_ball_is_pocketed: bool) -> Option<Foul> {
+        let cue_number = self.cue_ball_idx.map_or(0, |idx| self.balls[idx].number);
+
+        if was_break_shot && !called_ball_potted && self.shot_log.distinct_balls_to_cushion(&[cue_number]) < 2 {
+            return Some(Foul::IllegalBreak);
+        }
+        if cue_ball_is_pocketed {
+            return Some(Foul::Scratch);
+        }
+        if self.shot_log.first_contact(cue_number).is_none() {
+            return Some(Foul::NoContact);
+        }
+        if self.potted_ball_numbers_this_turn.is_empty() && !self.shot_log.cushion_after_first_contact(cue_number) {
+            return Some(Foul::NoRail);
+        }
+        None
+    }
+
+    fn object_balls_on_table(&self) -> usize {
+        self.balls.iter().filter(|ball| !ball.in_pocket && ball.number != 0).count()
+    }
+
+    // Volta as 14 bolas encaçapadas para o triângulo, com o ápice vazio. A 15ª e a
+    // branca que atrapalham o triângulo saem dele como no 14.1: a 15ª vai para o head
+    // spot (center spot, com a branca lá) e a branca fica na mão na área de saída, ou
+    // vai para o head spot (center spot, com a 15ª lá) quando a 15ª está na área de saída.
+    // Retorna true se a branca ficou na mão.
+    fn rerack_straight_pool(&mut self) -> bool {
+        let positions = self.rack_positions();
+        let touch_dist_sq = (self.ball_radius * 2.0).powi(2);
+        let touches = |a: Vec2, b: Vec2| a.distance_squared(b) < touch_dist_sq;
+        let in_rack_area = |pos: Vec2| positions.iter().any(|&rack_pos| touches(rack_pos, pos));
+
+        let cue_pos = self.cue_ball_idx.map(|idx| &self.balls[idx]).filter(|ball| !ball.in_pocket).map(|ball| ball.pos);
+        let last_ball_idx = self.balls.iter().position(|ball| !ball.in_pocket && ball.number != 0);
+        let last_pos = last_ball_idx.map(|idx| self.balls[idx].pos);
+        let cue_in_rack = cue_pos.is_some_and(in_rack_area);
+        let last_ball_in_rack = last_pos.is_some_and(in_rack_area);
+
+        // Sem bola restante (a 15ª caiu na mesma tacada) o triângulo é completo
+        let free_slots = if last_ball_idx.is_some() { &positions[1..] } else { &positions[..] };
+        let pocketed: Vec<usize> = (0..self.balls.len())
+            .filter(|&idx| self.balls[idx].in_pocket && self.balls[idx].number != 0)
+            .collect();
+        for (&ball_idx, &pos) in pocketed.iter().zip(free_slots.iter()) {
+            self.place_ball(ball_idx, pos);
+        }
+        self.clear_pocketed_balls();
+
+        let head_spot = self.head_spot();
+        let center_spot = self.center_spot();
+        if let Some(idx) = last_ball_idx.filter(|_| last_ball_in_rack) {
+            let cue_on_head_spot = !cue_in_rack && cue_pos.is_some_and(|pos| touches(pos, head_spot));
+            self.place_ball(idx, if cue_on_head_spot { center_spot } else { head_spot });
+            return cue_in_rack;
+        }
+        if !cue_in_rack {
+            return false;
+        }
+        match last_pos {
+            Some(pos) if pos.x < self.baulk_line_x() || touches(pos, head_spot) => {
+                let spot = if touches(pos, head_spot) { center_spot } else { head_spot };
+                if let Some(cb_idx) = self.cue_ball_idx {
+                    self.place_ball(cb_idx, spot);
+                }
+                false
+            }
+            _ => true,
+        }
+    }
+
+    // Triângulo completo para a nova saída depois da terceira falta seguida
+    fn rerack_straight_pool_full(&mut self) {
+        let positions = self.rack_positions();
+        let object_balls: Vec<usize> = (0..self.balls.len())
+            .filter(|&idx| self.balls[idx].number != 0)
+            .collect();
+        for (&ball_idx, &pos) in object_balls.iter().zip(positions.iter()) {
+            self.place_ball(ball_idx, pos);
+        }
+        if let Some(cb_idx) = self.cue_ball_idx {
+            let head_spot = self.head_spot();
+            self.place_ball(cb_idx, head_spot);
+        }
+        self.clear_pocketed_balls();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+    use crate::Game;
+    use crate::notification::Notification;
+    use crate::shot::ShotEvent;
+    use crate::types::{GameState, GameVariant, PlayerId};
+
+    fn straight_pool() -> Game {
+        let mut game = Game::new();
+        game.variant = GameVariant::StraightPool;
+        game.new_match();
+        game.game_state = GameState::BallsMoving;
+        game.is_break_shot = false;
+        game.take_notifications();
+        game
+    }
+
+    fn ball_idx(game: &Game, number: u8) -> usize {
+        game.balls.iter().position(|ball| ball.number == number).unwrap()
+    }
+
+    fn pot(game: &mut Game, number: u8, pocket: usize) {
+        let idx = ball_idx(game, number);
+        game.balls[idx].in_pocket = true;
+        game.potted_ball_numbers_this_turn.push(number);
+        game.shot_log.record(ShotEvent::Pocketed(number, pocket));
+    }
+
+    // Só a 15 na mesa, e a branca
+    fn last_ball_game(last: Vec2, cue: Vec2) -> Game {
+        let mut game = straight_pool();
+        for ball in &mut game.balls {
+            ball.in_pocket = ball.number != 0 && ball.number != 15;
+        }
+        let last_idx = ball_idx(&game, 15);
+        game.place_ball(last_idx, last);
+        let cue_idx = game.cue_ball_idx.unwrap();
+        game.place_ball(cue_idx, cue);
+        game
+    }
+
+    fn assert_no_overlap(game: &Game) {
+        let on_table: Vec<(u8, Vec2)> = game.balls.iter().filter(|ball| !ball.in_pocket).map(|ball| (ball.number, ball.pos)).collect();
+        for (i, &(a, pos_a)) in on_table.iter().enumerate() {
+            for &(b, pos_b) in &on_table[i + 1..] {
+                assert!(pos_a.distance(pos_b) >= game.ball_radius * 2.0 - 1e-3, "bolas {} e {} encostadas", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn called_ball_scores_every_ball_potted_on_the_shot() {
+        let mut game = straight_pool();
+        game.called_ball = Some(3);
+        game.shot_log.record(ShotEvent::BallContact(0, 3));
+        pot(&mut game, 3, 0);
+        pot(&mut game, 5, 1);
+        game.handle_straight_pool_turn_end();
+
+        assert_eq!(game.players[0].score, 2);
+        assert_eq!(game.current_player, PlayerId(0));
+        assert!(game.notifications.contains(&Notification::Scored { player: PlayerId(0), points: 2 }));
+    }
+
+    #[test]
+    fn other_ball_without_the_called_one_scores_nothing() {
+        let mut game = straight_pool();
+        game.called_ball = Some(3);
+        game.shot_log.record(ShotEvent::BallContact(0, 5));
+        pot(&mut game, 5, 1);
+        game.handle_straight_pool_turn_end();
+
+        assert_eq!(game.players[0].score, 0);
+        assert_eq!(game.current_player, PlayerId(1));
+    }
+
+    #[test]
+    fn third_foul_costs_the_extra_penalty_and_reracks() {
+        let mut game = straight_pool();
+        game.game_state = GameState::Aiming;
+        assert!(!game.apply_straight_pool_foul(1));
+        assert!(!game.apply_straight_pool_foul(1));
+        assert!(game.apply_straight_pool_foul(1));
+
+        assert_eq!(game.players[0].score, -18);
+        assert_eq!(game.players[0].consecutive_fouls, 0);
+        assert!(game.is_break_shot);
+        assert_eq!(game.balls.iter().filter(|ball| !ball.in_pocket).count(), 16);
+    }
+
+    #[test]
+    fn last_ball_in_the_rack_goes_to_the_head_spot() {
+        let game = straight_pool();
+        let mut game = last_ball_game(game.foot_spot(), game.center_spot());
+        assert!(!game.rerack_straight_pool());
+        assert_eq!(game.balls[ball_idx(&game, 15)].pos, game.head_spot());
+        assert_no_overlap(&game);
+    }
+
+    #[test]
+    fn last_ball_goes_to_the_center_spot_when_the_cue_ball_is_on_the_head_spot() {
+        let game = straight_pool();
+        let mut game = last_ball_game(game.foot_spot(), game.head_spot());
+        assert!(!game.rerack_straight_pool());
+        assert_eq!(game.balls[ball_idx(&game, 15)].pos, game.center_spot());
+        assert_no_overlap(&game);
+    }
+
+    #[test]
+    fn cue_ball_in_the_rack_goes_to_the_center_spot_when_the_last_ball_is_on_the_head_spot() {
+        let game = straight_pool();
+        let mut game = last_ball_game(game.head_spot(), game.foot_spot());
+        assert!(!game.rerack_straight_pool());
+        assert_eq!(game.balls[game.cue_ball_idx.unwrap()].pos, game.center_spot());
+        assert_no_overlap(&game);
+    }
+
+    #[test]
+    fn cue_ball_and_last_ball_in_the_rack_give_ball_in_hand() {
+        let game = straight_pool();
+        let rack = game.rack_positions();
+        let mut game = last_ball_game(rack[14], rack[4]);
+        assert!(game.rerack_straight_pool());
+        assert_eq!(game.balls[ball_idx(&game, 15)].pos, game.head_spot());
+    }
+}
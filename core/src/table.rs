@@ -0,0 +1,307 @@
+use std::sync::OnceLock;
+
+use glam::{Vec2, vec2};
+
+use crate::types::GameVariant;
+
+// Mesas definidas em dados. Cada mesa é um arquivo no formato dos catálogos
+// (`chave = valor`, uma por linha); as que vêm com o jogo ficam em tables/ e o
+// front end pode trazer outras. Medidas em milímetros na área de jogo, entre os
+// narizes das tabelas: x ao longo do comprimento a partir da tabela de saída.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableSpec {
+    pub name: String,
+    // Nome na tela por código de idioma
+    pub labels: Vec<(String, String)>,
+    pub kind: TableKind,
+    pub length: f32,
+    pub width: f32,
+    pub pockets: Vec<PocketSpec>,
+    pub cushion: CushionProfile,
+    // Limite da área de saída (kitchen, ou baulk no snooker)
+    pub head_string: f32,
+    // Raio do D do snooker; 0 nas mesas sem D
+    pub d_radius: f32,
+    // Linhas desenhadas no pano, atravessando a mesa na posição x
+    pub lines: Vec<f32>,
+    pub spots: Vec<(String, Vec2)>,
+    // Divisões marcadas na tabela longa e na curta
+    pub diamonds: (u32, u32),
+}
+
+// Que modalidades jogam na mesa
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableKind {
+    Pool,
+    Snooker,
+    Carom,
+}
+
+impl TableKind {
+    pub fn of(variant: GameVariant) -> TableKind {
+        match variant {
+            GameVariant::Snooker => TableKind::Snooker,
+            GameVariant::ThreeCushion => TableKind::Carom,
+            _ => TableKind::Pool,
+        }
+    }
+
+    fn parse(name: &str) -> Option<TableKind> {
+        match name {
+            "pool" => Some(TableKind::Pool),
+            "snooker" => Some(TableKind::Snooker),
+            "carom" => Some(TableKind::Carom),
+            _ => None,
+        }
+    }
+}
+
+// Por que um arquivo de mesa não serve; o front end escreve a mensagem
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableError {
+    // Linha sem `=` ou com chave desconhecida
+    Line(String),
+    Value { key: String, value: String },
+    Missing(String),
+    // Caçapa fora da linha das tabelas
+    Pocket(Vec2),
+}
+
+// Caçapa: o ponto na linha das tabelas (o canto, nas de canto), a largura da boca
+// entre as pontas das tabelas e quanto o centro da bola passa da boca antes de cair
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PocketSpec {
+    pub pos: Vec2,
+    pub mouth: f32,
+    pub shelf: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CushionProfile {
+    // Da ponta da borracha até a madeira
+    pub width: f32,
+    // Multiplica a elasticidade da tabela do perfil de física
+    pub rebound: f32,
+}
+
+// Snooker sem estes pontos não arma o rack
+const SNOOKER_SPOTS: [&str; 6] = ["yellow", "green", "brown", "blue", "pink", "black"];
+
+const BUILTIN_SOURCES: [&str; 5] = [
+    include_str!("../tables/pool_9ft.txt"),
+    include_str!("../tables/pool_8ft.txt"),
+    include_str!("../tables/pool_7ft.txt"),
+    include_str!("../tables/snooker_12ft.txt"),
+    include_str!("../tables/carom.txt"),
+];
+
+static BUILTIN: OnceLock<Vec<TableSpec>> = OnceLock::new();
+
+// Só para uma mesa embutida que não leu: um pano liso de 9 pés, sem caçapas
+impl Default for TableSpec {
+    fn default() -> Self {
+        TableSpec {
+            name: String::new(),
+            labels: Vec::new(),
+            kind: TableKind::Pool,
+            length: 2540.0,
+            width: 1270.0,
+            pockets: Vec::new(),
+            cushion: CushionProfile { width: 51.0, rebound: 1.0 },
+            head_string: 635.0,
+            d_radius: 0.0,
+            lines: Vec::new(),
+            spots: Vec::new(),
+            diamonds: (0, 0),
+        }
+    }
+}
+
+impl TableSpec {
+    // Mesas que vêm com o jogo, na ordem do menu
+    pub fn builtin() -> &'static [TableSpec] {
+        BUILTIN.get_or_init(|| BUILTIN_SOURCES.iter().filter_map(|source| TableSpec::parse(source).ok()).collect())
+    }
+
+    // Mesa de cada modalidade quando ninguém escolheu outra
+    pub fn standard_name(variant: GameVariant) -> &'static str {
+        match variant {
+            GameVariant::Snooker => "snooker_12ft",
+            GameVariant::ThreeCushion => "carom",
+            GameVariant::Blackball => "pool_7ft",
+            _ => "pool_9ft",
+        }
+    }
+
+    pub fn standard(variant: GameVariant) -> TableSpec {
+        let name = Self::standard_name(variant);
+        Self::builtin().iter().find(|table| table.name == name).cloned().unwrap_or_default()
+    }
+
+    pub fn fits(&self, variant: GameVariant) -> bool {
+        self.kind == TableKind::of(variant)
+    }
+
+    pub fn spot(&self, name: &str) -> Option<Vec2> {
+        self.spots.iter().find(|(spot, _)| spot == name).map(|&(_, pos)| pos)
+    }
+
+    pub fn parse(source: &str) -> Result<TableSpec, TableError> {
+        let mut table = TableSpec::default();
+        let mut name = None;
+        let mut kind = None;
+        let mut surface = None;
+        for line in source.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let Some((key, value)) = line.split_once('=').map(|(key, value)| (key.trim(), value.trim())) else {
+                return Err(TableError::Line(line.to_string()));
+            };
+            let invalid = || TableError::Value { key: key.to_string(), value: value.to_string() };
+            let numbers = |count: usize| -> Result<Vec<f32>, TableError> {
+                let values = value.split_whitespace().map(str::parse::<f32>).collect::<Result<Vec<_>, _>>().map_err(|_| invalid())?;
+                if values.len() != count || values.iter().any(|n| !n.is_finite() || *n < 0.0) { return Err(invalid()); }
+                Ok(values)
+            };
+            match key {
+                "name" => name = Some(value.to_string()),
+                "kind" => kind = Some(TableKind::parse(value).ok_or_else(invalid)?),
+                "surface" => {
+                    let n = numbers(2)?;
+                    if n[0] <= 0.0 || n[1] <= 0.0 { return Err(invalid()); }
+                    surface = Some((n[0], n[1]));
+                }
+                "pocket" => {
+                    let n = numbers(4)?;
+                    table.pockets.push(PocketSpec { pos: vec2(n[0], n[1]), mouth: n[2], shelf: n[3] });
+                }
+                "cushion.width" => table.cushion.width = numbers(1)?[0],
+                "cushion.rebound" => table.cushion.rebound = numbers(1)?[0].min(1.0),
+                "head_string" => table.head_string = numbers(1)?[0],
+                "d_radius" => table.d_radius = numbers(1)?[0],
+                "line" => table.lines.push(numbers(1)?[0]),
+                "diamonds" => {
+                    let n = numbers(2)?;
+                    table.diamonds = (n[0] as u32, n[1] as u32);
+                }
+                _ if key.starts_with("label.") => table.labels.push((key["label.".len()..].to_string(), value.to_string())),
+                _ if key.starts_with("spot.") => {
+                    let n = numbers(2)?;
+                    table.spots.push((key["spot.".len()..].to_string(), vec2(n[0], n[1])));
+                }
+                _ => return Err(TableError::Line(line.to_string())),
+            }
+        }
+        table.name = name.ok_or_else(|| TableError::Missing("name".to_string()))?;
+        table.kind = kind.ok_or_else(|| TableError::Missing("kind".to_string()))?;
+        (table.length, table.width) = surface.ok_or_else(|| TableError::Missing("surface".to_string()))?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    // Caçapa fora da linha das tabelas e ponto fora da mesa não têm como funcionar
+    fn validate(&self) -> Result<(), TableError> {
+        let on_table = |pos: Vec2| pos.x <= self.length && pos.y <= self.width;
+        for pocket in &self.pockets {
+            let on_rail = pocket.pos.x == 0.0 || pocket.pos.x == self.length || pocket.pos.y == 0.0 || pocket.pos.y == self.width;
+            if !on_rail || !on_table(pocket.pos) || pocket.mouth <= 0.0 {
+                return Err(TableError::Pocket(pocket.pos));
+            }
+        }
+        if let Some((name, pos)) = self.spots.iter().find(|(_, pos)| !on_table(*pos)) {
+            return Err(TableError::Value { key: format!("spot.{}", name), value: format!("{} {}", pos.x, pos.y) });
+        }
+        if self.kind == TableKind::Snooker {
+            if self.d_radius <= 0.0 {
+                return Err(TableError::Missing("d_radius".to_string()));
+            }
+            if let Some(spot) = SNOOKER_SPOTS.iter().find(|&&spot| self.spot(spot).is_none()) {
+                return Err(TableError::Missing(format!("spot.{}", spot)));
+            }
+        }
+        Ok(())
+    }
+
+    // Nome no idioma pedido (código como "pt"); sem tradução, o primeiro que houver
+    // ou o nome do arquivo
+    pub fn label(&self, language: &str) -> &str {
+        self.labels.iter()
+            .find(|(code, _)| code == language)
+            .or(self.labels.first())
+            .map_or(self.name.as_str(), |(_, label)| label.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec2;
+    use super::{TableError, TableKind, TableSpec};
+    use crate::types::GameVariant;
+
+    const MINIMAL: &str = "name = test\nkind = pool\nsurface = 2540 1270\n";
+
+    fn parse_with(extra: &str) -> Result<TableSpec, TableError> {
+        TableSpec::parse(&format!("{}{}", MINIMAL, extra))
+    }
+
+    #[test]
+    fn builtin_tables_all_parse() {
+        assert_eq!(TableSpec::builtin().len(), 5);
+        for variant in [GameVariant::EightBall, GameVariant::Snooker, GameVariant::ThreeCushion, GameVariant::Blackball] {
+            let table = TableSpec::standard(variant);
+            assert_eq!(table.name, TableSpec::standard_name(variant));
+            assert!(table.fits(variant));
+        }
+    }
+
+    #[test]
+    fn minimal_table_parses() {
+        let table = parse_with("pocket = 0 0 116 40\nspot.head = 635 635\n").unwrap();
+        assert_eq!(table.kind, TableKind::Pool);
+        assert_eq!((table.length, table.width), (2540.0, 1270.0));
+        assert_eq!(table.spot("head"), Some(vec2(635.0, 635.0)));
+        assert_eq!(table.pockets.len(), 1);
+    }
+
+    #[test]
+    fn line_without_a_key_or_with_an_unknown_one_is_refused() {
+        assert_eq!(parse_with("pocket 0 0 116 40\n").unwrap_err(), TableError::Line("pocket 0 0 116 40".to_string()));
+        assert_eq!(parse_with("felt = green\n").unwrap_err(), TableError::Line("felt = green".to_string()));
+    }
+
+    #[test]
+    fn bad_values_are_refused() {
+        let value = |key: &str, value: &str| TableError::Value { key: key.to_string(), value: value.to_string() };
+        assert_eq!(parse_with("pocket = 0 0 116\n").unwrap_err(), value("pocket", "0 0 116"));
+        assert_eq!(parse_with("head_string = -1\n").unwrap_err(), value("head_string", "-1"));
+        assert_eq!(parse_with("kind = croquet\n").unwrap_err(), value("kind", "croquet"));
+        assert_eq!(TableSpec::parse("name = t\nkind = pool\nsurface = 0 1270\n").unwrap_err(), value("surface", "0 1270"));
+    }
+
+    #[test]
+    fn required_keys_are_checked() {
+        assert_eq!(TableSpec::parse("kind = pool\nsurface = 2540 1270\n").unwrap_err(), TableError::Missing("name".to_string()));
+        assert_eq!(TableSpec::parse("name = t\nsurface = 2540 1270\n").unwrap_err(), TableError::Missing("kind".to_string()));
+        assert_eq!(TableSpec::parse("name = t\nkind = pool\n").unwrap_err(), TableError::Missing("surface".to_string()));
+    }
+
+    #[test]
+    fn pockets_and_spots_must_be_on_the_table() {
+        assert_eq!(parse_with("pocket = 100 100 116 40\n").unwrap_err(), TableError::Pocket(vec2(100.0, 100.0)));
+        assert_eq!(parse_with("pocket = 0 0 0 40\n").unwrap_err(), TableError::Pocket(vec2(0.0, 0.0)));
+        assert_eq!(
+            parse_with("spot.foot = 3000 635\n").unwrap_err(),
+            TableError::Value { key: "spot.foot".to_string(), value: "3000 635".to_string() }
+        );
+    }
+
+    #[test]
+    fn snooker_table_needs_the_d_and_the_colour_spots() {
+        let snooker = "name = s\nkind = snooker\nsurface = 3569 1778\n";
+        assert_eq!(TableSpec::parse(snooker).unwrap_err(), TableError::Missing("d_radius".to_string()));
+        let with_d = format!("{}d_radius = 292\nspot.yellow = 737 1181\n", snooker);
+        assert_eq!(TableSpec::parse(&with_d).unwrap_err(), TableError::Missing("spot.green".to_string()));
+    }
+}
@@ -0,0 +1,110 @@
+// Índice do jogador em Game::players
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerId(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerGroup {
+    Solids,
+    Stripes,
+    Reds,
+    Yellows,
+    Range(u8, u8),
+    Undecided,
+    EightBall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameState {
+    Initializing,
+    Aiming,
+    Shooting,
+    BallsMoving,
+    RepositionCueBall,
+    BreakChoice, // Adversário decide entre rearmar ou aceitar a mesa depois de uma saída ilegal
+    GroupChoice, // Quem saiu escolhe o grupo depois de encaçapar lisas e listradas na saída
+    GameOver, // Fim do rack
+    MatchOver,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameVariant {
+    EightBall,
+    StraightPool,
+    Snooker,
+    Blackball,
+    ThreeCushion,
+    Cutthroat,
+    // Regras de um script (Game::script); fora do ciclo de next, que é só das embutidas
+    Scripted,
+}
+
+impl GameVariant {
+    pub fn next(&self) -> Self {
+        match self {
+            GameVariant::EightBall => GameVariant::StraightPool,
+            GameVariant::StraightPool => GameVariant::Snooker,
+            GameVariant::Snooker => GameVariant::Blackball,
+            GameVariant::Blackball => GameVariant::ThreeCushion,
+            GameVariant::ThreeCushion => GameVariant::Cutthroat,
+            GameVariant::Cutthroat | GameVariant::Scripted => GameVariant::EightBall,
+        }
+    }
+
+    // Quantidade de jogadores aceita pela modalidade
+    pub fn player_counts(&self) -> std::ops::RangeInclusive<usize> {
+        match self {
+            GameVariant::Cutthroat => 3..=5,
+            GameVariant::Scripted => 2..=4,
+            _ => 2..=2,
+        }
+    }
+
+    // Variantes em que a terceira falta seguida pode perder a partida. A regra vem
+    // do 9 e do 10 bolas, que o jogo não tem; aqui vale no cutthroat e, como regra
+    // da casa, no bola 8 (ver Game::three_foul_rule). O contínuo tem a pena própria
+    // da terceira falta e o snooker não tem nenhuma.
+    pub fn uses_three_foul_rule(&self) -> bool {
+        matches!(self, GameVariant::EightBall | GameVariant::Cutthroat)
+    }
+
+    // Modalidades em que o jogador precisa cantar a bola antes da tacada
+    pub fn uses_called_ball(&self) -> bool {
+        matches!(self, GameVariant::StraightPool | GameVariant::Snooker)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Foul {
+    Scratch,
+    NoContact,
+    NoRail,
+    IllegalBreak,
+    WrongBallFirst,
+    WrongBallPotted,
+    // Tempo da tacada esgotado
+    ShotClock,
+}
+
+// Onde a branca pode ser colocada quando o jogador tem a bola na mão
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlacementArea {
+    Kitchen,
+    D,
+    Anywhere,
+}
+
+// Bola da vez no snooker: vermelha, uma cor a cantar, ou as cores em sequência
+// depois que as vermelhas acabam.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnookerPhase {
+    Red,
+    Colour,
+    Clearance(u8),
+}
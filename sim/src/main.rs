@@ -0,0 +1,108 @@
+// Simulador em lote: lê uma posição e uma lista de tacadas (JSON ou RON), joga
+// tudo com a física e as regras do núcleo, sem janela, e escreve o resultado em JSON.
+//
+//     potshot-sim <entrada.json|entrada.ron> [saida.json]
+//
+// Sem arquivo de saída o resultado vai para a saída padrão.
+
+mod report;
+mod scenario;
+
+use potshot_core::Game;
+use potshot_core::state_machine::GameCommand;
+use potshot_core::types::{GameState, PlayerId};
+
+use report::{Report, ShotReport, StateReport, TableReport};
+use scenario::{Decision, Scenario, ShotSpec};
+
+// Passo de tempo de cada quadro, igual ao de uma tela a 60 Hz
+const FRAME_DT: f32 = 1.0 / 60.0;
+// Limite de quadros por tacada, para uma física que não para nunca não travar o lote
+const MAX_FRAMES_PER_SHOT: u32 = 60 * 120;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("uso: potshot-sim <entrada.json|entrada.ron> [saida.json]");
+        std::process::exit(2);
+    }
+    if let Err(e) = run(&args[0], args.get(1).map(String::as_str)) {
+        eprintln!("potshot-sim: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(input_path: &str, output_path: Option<&str>) -> Result<(), String> {
+    let text = std::fs::read_to_string(input_path)
+        .map_err(|e| format!("não foi possível ler {}: {}", input_path, e))?;
+    let scenario = Scenario::parse(input_path, &text)?;
+    let mut game = scenario.build_game()?;
+
+    let table = TableReport::of(&game);
+    // Os avisos da montagem da mesa não são de nenhuma tacada
+    game.take_notifications();
+    let mut shots = Vec::with_capacity(scenario.shots.len());
+    for (index, shot) in scenario.shots.iter().enumerate() {
+        match play_shot(&mut game, shot) {
+            Ok((shooter, frames)) => shots.push(ShotReport::new(index, shooter, frames, &mut game)),
+            Err(e) => {
+                shots.push(ShotReport::failed(index, &mut game, e));
+                break;
+            }
+        }
+    }
+    let report = Report { table, shots, final_state: StateReport::of(&game) };
+
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    match output_path {
+        Some(path) => std::fs::write(path, json).map_err(|e| format!("não foi possível gravar {}: {}", path, e)),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    }
+}
+
+// Joga uma tacada até as bolas pararem e as regras decidirem a vez.
+// Retorna quem bateu e quantos quadros a física levou.
+fn play_shot(game: &mut Game, shot: &ShotSpec) -> Result<(PlayerId, u32), String> {
+    if let Some(decision) = shot.decision {
+        let command = match decision {
+            Decision::Rerack => GameCommand::Rerack,
+            Decision::AcceptTable => GameCommand::AcceptTable,
+            Decision::Group(group) => GameCommand::ChooseGroup(group),
+        };
+        game.execute(command).map_err(|e| format!("escolha {:?} recusada: {:?}", decision, e))?;
+    }
+    if game.game_state() == GameState::RepositionCueBall {
+        let Some([x, y]) = shot.cue_ball else {
+            return Err("o jogador tem a bola na mão, mas a tacada não traz cue_ball".to_string());
+        };
+        game.execute(GameCommand::PlaceCueBall(potshot_core::vec2(x, y)))
+            .map_err(|e| format!("posição da branca recusada: {:?} {}", e, last_notification(game)))?;
+    }
+    // Cantar fora da variante que canta não impede a tacada
+    if let Some(number) = shot.call_ball {
+        let _ = game.execute(GameCommand::CallBall(number));
+    }
+    if let Some(pocket) = shot.call_pocket {
+        let _ = game.execute(GameCommand::CallPocket(pocket));
+    }
+
+    let shooter = game.current_player;
+    game.execute(GameCommand::Shoot { angle: shot.angle, power: shot.power })
+        .map_err(|e| format!("tacada recusada: {:?} {}", e, last_notification(game)))?;
+    let mut frames = 0;
+    while matches!(game.game_state(), GameState::Shooting | GameState::BallsMoving) {
+        if frames >= MAX_FRAMES_PER_SHOT {
+            return Err(format!("as bolas não pararam em {} quadros", MAX_FRAMES_PER_SHOT));
+        }
+        game.update(FRAME_DT);
+        frames += 1;
+    }
+    Ok((shooter, frames))
+}
+
+fn last_notification(game: &Game) -> String {
+    game.notifications.last().map_or_else(String::new, |notification| format!("{:?}", notification))
+}
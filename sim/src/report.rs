@@ -0,0 +1,137 @@
+use serde::Serialize;
+
+use potshot_core::Game;
+use potshot_core::notification::Notification;
+use potshot_core::shot::ShotEvent;
+use potshot_core::types::{Foul, GameState, PlayerGroup, PlayerId};
+
+// Resultado gravado em JSON: a mesa usada, o que aconteceu em cada tacada e o
+// estado final
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub table: TableReport,
+    pub shots: Vec<ShotReport>,
+    pub final_state: StateReport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableReport {
+    pub name: String,
+    pub width: f32,
+    pub height: f32,
+    pub ball_radius: f32,
+}
+
+impl TableReport {
+    pub fn of(game: &Game) -> Self {
+        TableReport { name: game.table.name.clone(), width: game.table_width, height: game.table_height, ball_radius: game.ball_radius }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShotReport {
+    pub index: usize,
+    pub shooter: PlayerId,
+    // Preenchido quando a tacada não pôde ser jogada; o simulador para aí
+    pub error: Option<String>,
+    pub frames: u32,
+    pub pocketed: Vec<PottedBall>,
+    pub foul: Option<Foul>,
+    pub next_player: PlayerId,
+    pub state: GameState,
+    pub notifications: Vec<Notification>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PottedBall {
+    pub ball: u8,
+    pub pocket: usize,
+}
+
+impl ShotReport {
+    pub fn new(index: usize, shooter: PlayerId, frames: u32, game: &mut Game) -> Self {
+        let pocketed = game.shot_log.events.iter()
+            .filter_map(|event| match *event {
+                ShotEvent::Pocketed(ball, pocket) => Some(PottedBall { ball, pocket }),
+                _ => None,
+            })
+            .collect();
+        ShotReport {
+            index,
+            shooter,
+            error: None,
+            frames,
+            pocketed,
+            foul: game.shot_foul,
+            next_player: game.current_player,
+            state: game.game_state(),
+            notifications: game.take_notifications(),
+        }
+    }
+
+    pub fn failed(index: usize, game: &mut Game, error: String) -> Self {
+        ShotReport {
+            index,
+            shooter: game.current_player,
+            error: Some(error),
+            frames: 0,
+            pocketed: Vec::new(),
+            foul: None,
+            next_player: game.current_player,
+            state: game.game_state(),
+            notifications: game.take_notifications(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StateReport {
+    pub state: GameState,
+    pub current_player: PlayerId,
+    pub is_break_shot: bool,
+    pub balls: Vec<BallReport>,
+    pub players: Vec<PlayerReport>,
+    pub match_score: Vec<u32>,
+    pub rack_winner: Option<PlayerId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BallReport {
+    pub number: u8,
+    pub x: f32,
+    pub y: f32,
+    pub in_pocket: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerReport {
+    pub group: PlayerGroup,
+    pub score: i32,
+    pub consecutive_fouls: u32,
+    pub eliminated: bool,
+    pub pocketed: Vec<u8>,
+}
+
+impl StateReport {
+    pub fn of(game: &Game) -> Self {
+        StateReport {
+            state: game.game_state(),
+            current_player: game.current_player,
+            is_break_shot: game.is_break_shot,
+            balls: game.balls.iter()
+                .map(|ball| BallReport { number: ball.number, x: ball.pos.x, y: ball.pos.y, in_pocket: ball.in_pocket })
+                .collect(),
+            players: game.players.iter()
+                .map(|player| PlayerReport {
+                    group: player.group,
+                    score: player.score,
+                    consecutive_fouls: player.consecutive_fouls,
+                    eliminated: player.eliminated,
+                    pocketed: player.pocketed_balls.iter().map(|def| def.number).collect(),
+                })
+                .collect(),
+            match_score: game.match_score.clone(),
+            rack_winner: game.rack_winner,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Deserialize;
+
+use potshot_core::{Game, vec2};
+use potshot_core::ball::Ball;
+use potshot_core::colors::ball_colors;
+use potshot_core::house_rules::{HouseRule, HouseRules, HouseRulesPreset};
+use potshot_core::rack::RackLooseness;
+use potshot_core::table::TableSpec;
+use potshot_core::types::{GameVariant, PlayerGroup, PlayerId};
+
+// Arquivo de entrada do simulador: a posição da mesa e a lista de tacadas.
+// Posições em unidades da mesa (origem no canto superior esquerdo, x ao longo do
+// comprimento); sem `balls` o jogo arma o rack normal da modalidade.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    #[serde(default = "default_variant")]
+    pub variant: GameVariant,
+    #[serde(default)]
+    pub players: Option<usize>,
+    // Nome de uma das mesas que vêm com o jogo; sem ele vale a padrão da modalidade
+    #[serde(default)]
+    pub table: Option<String>,
+    #[serde(default)]
+    pub house_rules: HouseRulesSpec,
+    // Semente do rack sorteado; sem ela vale a sequência padrão, igual a cada execução
+    #[serde(default)]
+    pub seed: Option<u32>,
+    #[serde(default)]
+    pub looseness: RackLooseness,
+    #[serde(default)]
+    pub balls: Option<Vec<BallSpec>>,
+    #[serde(default)]
+    pub current_player: usize,
+    // Grupo de cada jogador, na ordem dos jogadores
+    #[serde(default)]
+    pub groups: Vec<PlayerGroup>,
+    // Padrão: saída quando o rack é o normal, jogada comum numa posição montada
+    #[serde(default)]
+    pub break_shot: Option<bool>,
+    pub shots: Vec<ShotSpec>,
+}
+
+fn default_variant() -> GameVariant {
+    GameVariant::EightBall
+}
+
+// Regras da casa: um preset e, por cima dele, as regras que mudam
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HouseRulesSpec {
+    pub preset: HouseRulesPreset,
+    pub scratch_on_eight_loses: Option<bool>,
+    pub eight_on_break_wins: Option<bool>,
+    pub open_table_after_break: Option<bool>,
+    pub opponent_ball_is_foul: Option<bool>,
+    pub ball_in_hand_anywhere: Option<bool>,
+    pub call_the_eight: Option<bool>,
+    pub choose_group_after_mixed_break: Option<bool>,
+    pub three_foul_loss: Option<bool>,
+}
+
+impl HouseRulesSpec {
+    fn rules(&self) -> HouseRules {
+        let mut rules = self.preset.rules();
+        let overrides = [
+            (HouseRule::ScratchOnEightLoses, self.scratch_on_eight_loses),
+            (HouseRule::EightOnBreakWins, self.eight_on_break_wins),
+            (HouseRule::OpenTableAfterBreak, self.open_table_after_break),
+            (HouseRule::OpponentBallIsFoul, self.opponent_ball_is_foul),
+            (HouseRule::BallInHandAnywhere, self.ball_in_hand_anywhere),
+            (HouseRule::CallTheEight, self.call_the_eight),
+            (HouseRule::ChooseGroupAfterMixedBreak, self.choose_group_after_mixed_break),
+            (HouseRule::ThreeFoulLoss, self.three_foul_loss),
+        ];
+        for (rule, on) in overrides {
+            if let Some(on) = on {
+                rules.set(rule, on);
+            }
+        }
+        rules
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BallSpec {
+    pub number: u8,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShotSpec {
+    pub angle: f32, // radianos; 0 aponta para a tabela de fundo
+    pub power: f32, // 0 a 1
+    // Onde colocar a branca quando o jogador tem a bola na mão
+    #[serde(default)]
+    pub cue_ball: Option<[f32; 2]>,
+    #[serde(default)]
+    pub call_ball: Option<u8>,
+    #[serde(default)]
+    pub call_pocket: Option<usize>,
+    // Resposta a uma escolha pendente (saída ilegal, grupo) antes da tacada
+    #[serde(default)]
+    pub decision: Option<Decision>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum Decision {
+    Rerack,
+    AcceptTable,
+    Group(PlayerGroup),
+}
+
+impl Scenario {
+    pub fn parse(path: &str, text: &str) -> Result<Scenario, String> {
+        if path.ends_with(".ron") {
+            ron::from_str(text).map_err(|e| format!("RON inválido em {}: {}", path, e))
+        } else {
+            serde_json::from_str(text).map_err(|e| format!("JSON inválido em {}: {}", path, e))
+        }
+    }
+
+    // Monta o jogo com a modalidade e a posição do arquivo
+    pub fn build_game(&self) -> Result<Game, String> {
+        let mut game = Game::new();
+        game.variant = self.variant;
+        game.house_rules = self.house_rules.rules();
+        game.rack_options.seed = self.seed;
+        game.rack_options.looseness = self.looseness;
+        if let Some(players) = self.players {
+            if !self.variant.player_counts().contains(&players) {
+                return Err(format!("{:?} não aceita {} jogadores", self.variant, players));
+            }
+            game.player_count = players;
+        }
+        if let Some(name) = &self.table {
+            let table = TableSpec::builtin().iter()
+                .find(|table| &table.name == name)
+                .ok_or_else(|| format!("mesa {} não existe", name))?;
+            if !table.fits(self.variant) {
+                return Err(format!("a mesa {} não serve para {:?}", name, self.variant));
+            }
+            game.table = table.clone();
+        }
+        game.new_match();
+
+        if let Some(balls) = &self.balls {
+            let radius = game.ball_radius;
+            let mut placed = Vec::with_capacity(balls.len());
+            for spec in balls {
+                let pos = vec2(spec.x, spec.y);
+                let ball = match game.ball_definitions_map.get(&spec.number) {
+                    Some(def) => Ball::from_definition(pos, def, radius),
+                    None if spec.number == 0 => Ball::new(pos.x, pos.y, ball_colors::CUE, 0, false, radius),
+                    None => return Err(format!("bola {} não existe em {:?}", spec.number, self.variant)),
+                };
+                placed.push(ball);
+            }
+            game.balls = placed;
+            game.is_break_shot = false;
+        }
+        if let Some(break_shot) = self.break_shot {
+            game.is_break_shot = break_shot;
+        }
+
+        if self.current_player >= game.players.len() {
+            return Err(format!("current_player {} fora da partida de {} jogadores", self.current_player, game.players.len()));
+        }
+        game.current_player = PlayerId(self.current_player);
+        for (player, group) in game.players.iter_mut().zip(&self.groups) {
+            player.group = *group;
+        }
+        game.sync_cue_ball_to_current_player();
+        Ok(game)
+    }
+}
@@ -0,0 +1,779 @@
+use macroquad::prelude::*;
+
+use potshot_core::Game;
+use potshot_core::history::{ShotHistory, ShotSnapshot};
+use potshot_core::notification::Notification;
+use potshot_core::replay::{Replay, ReplayPlayer};
+use potshot_core::script::ScriptedVariant;
+use potshot_core::table::TableSpec;
+use potshot_core::state_machine::GameCommand;
+use potshot_core::types::{GameState, PlayerGroup, PlayerId};
+use crate::i18n;
+use crate::assets::{asset_dirs, load_font, load_tables, load_variants};
+use crate::cue::Cue;
+use crate::Scene;
+use crate::menus::GameSetup;
+use crate::notices::Notice;
+use crate::save::{read_replay, read_save, read_settings, remove_save, settings_path, write_replay, write_save, write_settings};
+use crate::settings::{Settings, SettingsWatch};
+
+// --- Estruturas de Input ---
+#[derive(Default, Debug, Clone, Copy)]
+pub struct InputState {
+    pub is_dragging: bool,
+    pub start_pos: Option<Vec2>,
+    pub current_pos: Vec2,
+}
+
+// Replay em exibição. O Game reproduzido fica em App::game para ser desenhado como
+// qualquer partida; a partida ao vivo espera aqui até o replay fechar.
+pub struct ReplayView {
+    pub player: ReplayPlayer,
+    live_game: Game,
+}
+
+// Front end do jogo: guarda o Game do núcleo e tudo que só existe na tela
+// (fonte, layout, botões, taco e input)
+pub struct App {
+    pub game: Game,
+    // None quando nenhuma fonte abriu: o texto sai com a fonte padrão do macroquad
+    pub font: Option<Font>,
+
+    // Dimensões na tela
+    pub scale: f32, // pixels por unidade da mesa
+    pub table_border_thickness: f32,
+    pub cue_max_length: f32,
+    pub cue_width: f32,
+    pub table_offset: Vec2,
+    pub game_area_offset: Vec2,
+
+    pub cue: Cue,
+    // Linha de botões do HUD: no replay são os controles, ao vivo só o menu no meio
+    pub hud_row_rects: [Rect; 5],
+    pub pause_button_rect: Rect,
+    pub choice_left_button_rect: Rect,
+    pub choice_right_button_rect: Rect,
+    pub extension_button_rect: Rect,
+    pub center_button_rect: Rect,
+    pub save_button_rect: Rect,
+    pub load_button_rect: Rect,
+    pub replay_button_rect: Rect,
+    pub seed_button_rect: Rect,
+    // Campo da semente em foco: só então os dígitos digitados editam a semente
+    pub seed_focus: bool,
+    pub language_button_rect: Rect,
+    pub undo_button_rect: Rect,
+    pub redo_button_rect: Rect,
+    pub input_state: InputState,
+    // Avisos na tela e os que chegaram neste quadro, que substituem os da tela
+    pub notices: Vec<Notice>,
+    pending_notices: Vec<Notice>,
+    pub replay: Option<ReplayView>,
+    pub history: ShotHistory,
+    // Cenas: opções da tela de nova partida, clique de menu em andamento, troca
+    // pedida neste quadro (main.rs aplica) e se há partida para continuar
+    pub setup: GameSetup,
+    pub menu_press: Option<Vec2>,
+    pub scene_change: Option<Scene>,
+    pub game_started: bool,
+    pub quit: bool,
+    // Configurações em uso, o rascunho da tela de configurações (só vale ao salvar),
+    // a cena para onde ela volta e a vigia do arquivo
+    pub settings: Settings,
+    pub settings_draft: Settings,
+    pub settings_return: Scene,
+    settings_watch: SettingsWatch,
+    // Variantes com script e mesas carregadas ao abrir o jogo, na ordem do menu
+    pub variants: Vec<ScriptedVariant>,
+    pub tables: Vec<TableSpec>,
+    // Bolas andando no quadro anterior; quando param a partida é salva
+    was_moving: bool,
+    // Estado e jogador da vez no último quadro; quando mudam por conta do jogo
+    // (relógio, fim de tacada) o arrasto em andamento é descartado
+    last_turn: (GameState, PlayerId),
+    // Rack encerrado no último quadro; ao encerrar, o replay do rack é gravado
+    was_rack_over: bool,
+}
+
+impl App {
+    pub async fn new() -> Self {
+        let mut pending_notices = Vec::new();
+        let settings = match read_settings() {
+            Some(Ok(settings)) => settings,
+            Some(Err(e)) => {
+                pending_notices.push(Notice::SettingsFailed(e));
+                Settings::default()
+            }
+            None => Settings::default(),
+        };
+        i18n::set_language(settings.language);
+        let dirs = asset_dirs(&settings.asset_dirs, &mut pending_notices);
+        let font = load_font(&dirs, &mut pending_notices).await;
+        let variants = load_variants(&dirs, &mut pending_notices);
+        let tables = load_tables(&dirs, &mut pending_notices);
+
+        // Retoma a partida salva, se houver
+        // Só retoma a partida em andamento; um save de partida já decidida fica de fora
+        let (game, game_started) = match read_save() {
+            Some(Ok(game)) if game.game_state() != GameState::MatchOver => {
+                pending_notices.push(Notice::Resumed);
+                (game, true)
+            }
+            Some(Err(e)) => {
+                warn!("Save ignorado: {}", e);
+                (Self::fresh_game(&settings, &variants, &tables), false)
+            }
+            _ => (Self::fresh_game(&settings, &variants, &tables), false),
+        };
+        let setup = settings.game.clone();
+        let last_turn = (game.game_state(), game.current_player);
+        let was_rack_over = matches!(game.game_state(), GameState::GameOver | GameState::MatchOver);
+        let mut app = App {
+            game,
+            font,
+            scale: 1.0,
+            table_border_thickness: 0.0,
+            cue_max_length: 0.0,
+            cue_width: 0.0,
+            table_offset: Vec2::ZERO,
+            game_area_offset: Vec2::ZERO,
+            cue: Cue::new(),
+            hud_row_rects: [Rect::new(0.0, 0.0, 0.0, 0.0); 5],
+            pause_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            choice_left_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            choice_right_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            extension_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            center_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            save_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            load_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            replay_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            seed_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            seed_focus: false,
+            language_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            undo_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            redo_button_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+            input_state: InputState::default(),
+            notices: Vec::new(),
+            pending_notices,
+            replay: None,
+            history: ShotHistory::new(),
+            setup,
+            menu_press: None,
+            scene_change: None,
+            game_started,
+            quit: false,
+            settings_draft: settings.clone(),
+            settings,
+            settings_return: Scene::MainMenu,
+            settings_watch: SettingsWatch::new(&settings_path()),
+            variants,
+            tables,
+            was_moving: false,
+            last_turn,
+            was_rack_over,
+        };
+        app.layout();
+        app
+    }
+
+    // Partida nova com as opções padrão das configurações e os racks sorteados a
+    // partir do relógio, para não repetir a mesma sequência a cada vez que o jogo abre
+    fn fresh_game(settings: &Settings, variants: &[ScriptedVariant], tables: &[TableSpec]) -> Game {
+        let mut game = Game::new();
+        settings.game.apply(&mut game, variants, tables);
+        game.physics = settings.physics;
+        game.rack_seed_state = (miniquad::date::now() * 1000.0) as u64;
+        game.new_match();
+        game
+    }
+
+    // Reinicia a partida no núcleo; a mesa pode ter mudado de proporção
+    pub fn new_match(&mut self) {
+        self.cancel_aim();
+        self.history.clear();
+        self.game.physics = self.settings.physics;
+        let _ = self.game.execute(GameCommand::NewMatch);
+        self.layout();
+    }
+
+    // Recalcula a escala da mesa e os botões para o tamanho atual da tela
+    pub fn layout(&mut self) {
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+        let min_dimension = screen_w.min(screen_h);
+        let length_to_width = self.game.table_width / self.game.table_height;
+
+        self.table_border_thickness = (min_dimension * 0.02).max(5.0);
+        self.cue_max_length = (min_dimension * 0.25).max(80.0);
+        self.cue_width = (min_dimension * 0.012).max(4.0);
+
+        let hud_height = screen_h * 0.15;
+        let available_height_for_table_and_message = screen_h - hud_height;
+        let message_area_height = screen_h * 0.08;
+        let table_area_max_width = screen_w - (self.table_border_thickness * 2.0) - screen_w * 0.02;
+        let table_area_max_height = available_height_for_table_and_message - message_area_height - (self.table_border_thickness * 2.0);
+
+        let width_from_h = table_area_max_height * length_to_width;
+        let table_width = table_area_max_width.min(width_from_h).max(screen_w * 0.3);
+        self.scale = table_width / self.game.table_width;
+
+        self.table_offset = vec2(
+            (screen_w - table_width - self.table_border_thickness * 2.0) / 2.0,
+            message_area_height,
+        );
+        self.game_area_offset = vec2(
+            self.table_offset.x + self.table_border_thickness,
+            self.table_offset.y + self.table_border_thickness,
+        );
+
+        let button_width = screen_w * 0.16;
+        let button_height = screen_h * 0.05;
+        let button_gap = screen_w * 0.02;
+        let hud_bottom_y = screen_h * 0.85 + screen_h * 0.025;
+
+        // Linha de botões do HUD: cinco lugares, o do meio é o menu da partida
+        let row_button_width = screen_w * 0.14;
+        let row_width = row_button_width * 5.0 + button_gap * 4.0;
+        let row_rect = |slot: usize| Rect::new(
+            (screen_w - row_width) / 2.0 + slot as f32 * (row_button_width + button_gap),
+            hud_bottom_y + button_height * 0.5,
+            row_button_width,
+            button_height,
+        );
+        self.hud_row_rects = std::array::from_fn(row_rect);
+        self.pause_button_rect = row_rect(2);
+        // Canto superior direito: idioma, carregar, salvar e, à esquerda deles, a
+        // extensão e a contagem do relógio
+        let small_button_width = button_width * 0.5;
+        self.language_button_rect = Rect::new(
+            screen_w - small_button_width - button_gap,
+            screen_h * 0.01,
+            small_button_width,
+            button_height,
+        );
+        self.load_button_rect = Rect::new(
+            self.language_button_rect.x - small_button_width - button_gap * 0.5,
+            screen_h * 0.01,
+            small_button_width,
+            button_height,
+        );
+        self.save_button_rect = Rect::new(
+            self.load_button_rect.x - small_button_width - button_gap * 0.5,
+            screen_h * 0.01,
+            small_button_width,
+            button_height,
+        );
+        self.extension_button_rect = Rect::new(
+            self.save_button_rect.x - button_width - button_gap,
+            screen_h * 0.01,
+            button_width,
+            button_height,
+        );
+        // Canto superior esquerdo: desfazer, refazer e a semente do rack
+        self.undo_button_rect = Rect::new(button_gap, screen_h * 0.01, small_button_width, button_height);
+        self.redo_button_rect = Rect::new(
+            self.undo_button_rect.x + small_button_width + button_gap * 0.5,
+            screen_h * 0.01,
+            small_button_width,
+            button_height,
+        );
+        self.seed_button_rect = Rect::new(
+            self.redo_button_rect.x + small_button_width + button_gap,
+            screen_h * 0.01,
+            button_width,
+            button_height,
+        );
+        // Escolhas no meio do rack (saída ilegal, grupo): dois botões no centro da mesa
+        let table_center = self.game_area_offset + self.table_size() / 2.0;
+        self.choice_left_button_rect = Rect::new(
+            table_center.x - button_width - screen_w * 0.01,
+            table_center.y - button_height / 2.0,
+            button_width,
+            button_height,
+        );
+        self.choice_right_button_rect = Rect::new(
+            table_center.x + screen_w * 0.01,
+            table_center.y - button_height / 2.0,
+            button_width,
+            button_height,
+        );
+        // Próximo rack e nova partida
+        self.center_button_rect = Rect::new(
+            table_center.x - button_width / 2.0,
+            table_center.y + button_height,
+            button_width,
+            button_height,
+        );
+        self.replay_button_rect = Rect::new(
+            self.center_button_rect.x,
+            self.center_button_rect.y + button_height * 1.5,
+            button_width,
+            button_height,
+        );
+    }
+
+    // Tamanho da área de jogo na tela
+    pub fn table_size(&self) -> Vec2 {
+        vec2(self.game.table_width, self.game.table_height) * self.scale
+    }
+
+    // Ponto da mesa para a tela e de volta
+    pub fn to_screen(&self, table_pos: Vec2) -> Vec2 {
+        self.game_area_offset + table_pos * self.scale
+    }
+
+    pub fn to_table(&self, screen_pos: Vec2) -> Vec2 {
+        (screen_pos - self.game_area_offset) / self.scale
+    }
+
+    // Descarta o arrasto em andamento para que um clique em botão não vire tacada
+    pub fn cancel_aim(&mut self) {
+        self.input_state.is_dragging = false;
+        self.input_state.start_pos = None;
+        self.cue.is_dragging = false;
+        self.cue.power = 0.0;
+    }
+
+    pub fn process_input(&mut self) {
+        let turn = (self.game.game_state(), self.game.current_player);
+        if turn != self.last_turn {
+            self.cancel_aim();
+            self.last_turn = turn;
+        }
+        // Enter e Esc tiram o foco do campo da semente, sem pausar
+        if self.seed_focus
+            && (is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) || is_key_pressed(KeyCode::Escape))
+        {
+            self.seed_focus = false;
+            return;
+        }
+        // A tecla de pausa sai do replay ou pausa a partida
+        if self.settings.bindings.pause.is_pressed() {
+            if self.replay.is_some() {
+                self.exit_replay();
+            } else {
+                self.change_scene(Scene::Paused);
+            }
+            return;
+        }
+
+        let input_pos = if let Some(touch) = touches().first() {
+            vec2(touch.position.x, touch.position.y)
+        } else {
+            mouse_position().into()
+        };
+
+        if is_mouse_button_pressed(MouseButton::Left) || (touches().len() == 1 && !self.input_state.is_dragging) {
+            self.input_state.is_dragging = true;
+            self.input_state.start_pos = Some(input_pos);
+        } else if !is_mouse_button_down(MouseButton::Left) && touches().is_empty() && self.input_state.is_dragging {
+            let is_click = self.input_state.start_pos.is_some_and(|start| start.distance(input_pos) < 5.0);
+            if is_click && self.handle_click(input_pos) {
+                self.autosave();
+                return;
+            }
+            self.input_state.is_dragging = false;
+        }
+        self.input_state.current_pos = input_pos;
+        // No replay a mesa só mostra; as tacadas vêm do arquivo
+        if self.replay.is_some() { return; }
+
+        if self.settings.bindings.undo.is_pressed() && self.step_history(true) {
+            self.autosave();
+            return;
+        }
+        if self.settings.bindings.redo.is_pressed() && self.step_history(false) {
+            self.autosave();
+            return;
+        }
+        if self.seed_focus && self.edit_rack_seed() {
+            self.autosave();
+        }
+
+        match self.game.game_state() {
+            GameState::RepositionCueBall => {
+                let pressed = is_mouse_button_pressed(MouseButton::Left)
+                    || (touches().len() == 1 && self.input_state.start_pos == Some(input_pos));
+                if pressed && self.game.execute(GameCommand::PlaceCueBall(self.to_table(input_pos))).is_ok() {
+                    self.autosave();
+                }
+            }
+            GameState::Aiming => self.process_aim(input_pos),
+            _ => {}
+        }
+    }
+
+    // Cliques nos botões e nas escolhas do HUD; retorna true quando o clique foi usado
+    fn handle_click(&mut self, input_pos: Vec2) -> bool {
+        // Clique fora do campo da semente tira o foco dele
+        if !self.seed_button_rect.contains(input_pos) {
+            self.seed_focus = false;
+        }
+        if self.replay.is_some() {
+            return self.handle_replay_click(input_pos);
+        }
+        if self.save_button_rect.contains(input_pos) {
+            self.cancel_aim();
+            self.notify(match write_save(&self.game) {
+                Ok(()) => Notice::Saved,
+                Err(e) => Notice::SaveFailed(e),
+            });
+            return true;
+        }
+        if self.load_button_rect.contains(input_pos) {
+            self.cancel_aim();
+            match read_save() {
+                Some(Ok(game)) => {
+                    self.replace_game(game);
+                    self.notify(Notice::Loaded);
+                }
+                Some(Err(e)) => self.notify(Notice::LoadFailed(e)),
+                None => self.notify(Notice::NoSave),
+            }
+            return true;
+        }
+        if self.language_button_rect.contains(input_pos) {
+            let mut settings = self.settings.clone();
+            settings.language = settings.language.next();
+            self.save_settings(settings);
+            return true;
+        }
+        if self.undo_button_rect.contains(input_pos) && self.step_history(true) {
+            return true;
+        }
+        if self.redo_button_rect.contains(input_pos) && self.step_history(false) {
+            return true;
+        }
+        if self.pause_button_rect.contains(input_pos) {
+            self.change_scene(Scene::Paused);
+            return true;
+        }
+        if self.seed_button_rect.contains(input_pos) {
+            self.cancel_aim();
+            // Semente livre: o clique fixa a do rack e dá foco ao campo. Fixa: o
+            // clique dá foco para editar e, com foco, volta ao sorteio.
+            match self.game.rack_options.seed {
+                None => {
+                    let seed = self.game.rack_seed;
+                    self.set_rack_seed(Some(seed));
+                    self.seed_focus = true;
+                    self.notify(Notice::SeedFixed(seed));
+                }
+                Some(seed) if !self.seed_focus => {
+                    self.seed_focus = true;
+                    self.notify(Notice::SeedEditing(seed));
+                }
+                Some(_) => {
+                    self.set_rack_seed(None);
+                    self.seed_focus = false;
+                    self.notify(Notice::SeedFree);
+                }
+            }
+            return true;
+        }
+        // O fim da partida tem a tela de resultado; aqui só o fim de um rack
+        if self.center_button_rect.contains(input_pos) && self.game.game_state() == GameState::GameOver {
+            self.cancel_aim();
+            let _ = self.game.execute(GameCommand::NextRack);
+            self.history.clear();
+            return true;
+        }
+        if self.replay_button_rect.contains(input_pos)
+            && self.game.game_state() == GameState::GameOver
+            && let Some(replay) = Replay::of_rack(&self.game)
+        {
+            self.enter_replay(replay);
+            return true;
+        }
+        if self.extension_button_rect.contains(input_pos) && self.game.can_use_extension() {
+            self.cancel_aim();
+            let _ = self.game.execute(GameCommand::UseExtension);
+            return true;
+        }
+        if self.game.game_state() == GameState::BreakChoice {
+            if self.choice_left_button_rect.contains(input_pos) {
+                self.cancel_aim();
+                let _ = self.game.execute(GameCommand::Rerack);
+                return true;
+            }
+            if self.choice_right_button_rect.contains(input_pos) {
+                self.cancel_aim();
+                let _ = self.game.execute(GameCommand::AcceptTable);
+                return true;
+            }
+        }
+        if self.game.game_state() == GameState::GroupChoice {
+            for (rect, group) in [(self.choice_left_button_rect, PlayerGroup::Solids), (self.choice_right_button_rect, PlayerGroup::Stripes)] {
+                if rect.contains(input_pos) {
+                    self.cancel_aim();
+                    let _ = self.game.execute(GameCommand::ChooseGroup(group));
+                    return true;
+                }
+            }
+        }
+        if self.game.game_state() == GameState::Aiming && self.game.must_call_eight()
+            && let Some(pocket_idx) = self.game.pocket_at(self.to_table(input_pos))
+        {
+            self.cancel_aim();
+            let _ = self.game.execute(GameCommand::CallPocket(pocket_idx));
+            return true;
+        }
+        if self.game.game_state() == GameState::Aiming
+            && let Some(number) = self.call_picker_hit(input_pos)
+        {
+            self.cancel_aim();
+            let _ = self.game.execute(GameCommand::CallBall(number));
+            return true;
+        }
+        false
+    }
+
+    // Arrastar a partir da branca define ângulo e força; soltar dá a tacada
+    fn process_aim(&mut self, input_pos: Vec2) {
+        let Some(cb) = self.game.cue_ball_idx.and_then(|idx| self.game.balls.get(idx)) else { return; };
+        if cb.in_pocket { return; }
+
+        let cue_ball_screen_pos = self.to_screen(cb.pos);
+
+        if self.input_state.is_dragging && self.input_state.start_pos.is_some() {
+            self.cue.is_dragging = true;
+            let dx = input_pos.x - cue_ball_screen_pos.x;
+            let dy = input_pos.y - cue_ball_screen_pos.y;
+            let dist_from_ball = (dx * dx + dy * dy).sqrt();
+
+            self.cue.angle = dy.atan2(dx);
+            self.cue.power = (dist_from_ball / self.cue_max_length).min(1.0);
+        } else if !self.input_state.is_dragging && self.cue.is_dragging { // Released
+            self.cue.is_dragging = false;
+            let before_shot = self.game.undo_allowed().then(|| ShotSnapshot::of(&self.game));
+            let shot = self.game.execute(GameCommand::Shoot { angle: self.cue.angle, power: self.cue.power });
+            if let Some(before_shot) = before_shot
+                && shot.is_ok()
+            {
+                self.history.record(before_shot);
+            }
+            self.cue.power = 0.0; // Reset power after shot
+        }
+    }
+
+    // Com o campo em foco, dígitos digitados editam a semente fixa e Backspace
+    // apaga o último; retorna true quando a semente mudou
+    fn edit_rack_seed(&mut self) -> bool {
+        let Some(mut seed) = self.game.rack_options.seed else {
+            self.seed_focus = false;
+            return false;
+        };
+        let mut changed = false;
+        while let Some(c) = get_char_pressed() {
+            if let Some(digit) = c.to_digit(10)
+                && let Some(next) = seed.checked_mul(10).and_then(|s| s.checked_add(digit))
+            {
+                seed = next;
+                changed = true;
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            seed /= 10;
+            changed = true;
+        }
+        if changed {
+            self.set_rack_seed(Some(seed));
+            self.notify(Notice::SeedSet(seed));
+        }
+        changed
+    }
+
+    // Uma semente que refaz o rack começa o histórico de novo, como um rack novo
+    fn set_rack_seed(&mut self, seed: Option<u32>) {
+        let rack_seed = self.game.rack_seed;
+        self.game.set_rack_seed(seed);
+        if self.game.rack_seed != rack_seed {
+            self.history.clear();
+        }
+    }
+
+    // Desfaz (true) ou refaz a última tacada; retorna true quando o jogo mudou
+    fn step_history(&mut self, undo: bool) -> bool {
+        if !self.game.undo_allowed() { return false; }
+        let changed = if undo { self.history.undo(&mut self.game) } else { self.history.redo(&mut self.game) };
+        if !changed { return false; }
+        self.cancel_aim();
+        // Refazer até o fim do rack não grava o replay de novo
+        self.was_rack_over = matches!(self.game.game_state(), GameState::GameOver | GameState::MatchOver);
+        self.notify(if undo { Notice::ShotUndone } else { Notice::ShotRedone });
+        true
+    }
+
+    // Controles do replay na linha de botões do HUD; clique fora deles não faz nada
+    fn handle_replay_click(&mut self, input_pos: Vec2) -> bool {
+        let Some(view) = self.replay.as_mut() else { return false; };
+        let [previous, play, next, speed, exit] = self.hud_row_rects;
+        if previous.contains(input_pos) {
+            view.player.previous_shot(&mut self.game);
+        } else if play.contains(input_pos) {
+            // No fim, tocar de novo recomeça do início do rack
+            if !view.player.playing && view.player.is_finished(&self.game) {
+                view.player.seek(&mut self.game, 0);
+            }
+            view.player.playing = !view.player.playing;
+        } else if next.contains(input_pos) {
+            view.player.next_shot(&mut self.game);
+        } else if speed.contains(input_pos) {
+            view.player.next_speed();
+        } else if exit.contains(input_pos) {
+            self.exit_replay();
+        } else {
+            return false;
+        }
+        true
+    }
+
+    // Troca a partida na tela pelo começo do replay
+    pub fn enter_replay(&mut self, replay: Replay) {
+        self.cancel_aim();
+        let player = ReplayPlayer::new(replay);
+        let live_game = std::mem::replace(&mut self.game, player.start_game());
+        self.replay = Some(ReplayView { player, live_game });
+        self.layout();
+    }
+
+    pub fn exit_replay(&mut self) {
+        let Some(view) = self.replay.take() else { return; };
+        self.game = view.live_game;
+        self.layout();
+    }
+
+    // Abre um replay gravado (potshot --replay <arquivo>)
+    pub fn open_replay_file(&mut self, path: &std::path::Path) {
+        match read_replay(path) {
+            Ok(replay) => self.enter_replay(replay),
+            Err(e) => self.notify(Notice::ReplayFailed(e)),
+        }
+    }
+
+    // Troca a partida inteira (carregar o save): o histórico e o layout eram da outra
+    pub fn replace_game(&mut self, game: Game) {
+        self.game = game;
+        self.history.clear();
+        self.was_rack_over = matches!(self.game.game_state(), GameState::GameOver | GameState::MatchOver);
+        self.layout();
+    }
+
+    // Pede a troca de cena; main.rs aplica no fim do quadro, com enter_scene
+    pub fn change_scene(&mut self, scene: Scene) {
+        self.scene_change = Some(scene);
+    }
+
+    // Entrar numa cena descarta o toque e o arrasto da anterior, para nada vazar de
+    // uma tela para outra. Saindo da mesa a partida fica salva.
+    pub fn enter_scene(&mut self, from: Scene, scene: Scene) {
+        self.cancel_aim();
+        self.input_state.is_dragging = false;
+        self.menu_press = None;
+        self.seed_focus = false;
+        match scene {
+            Scene::Settings => self.settings_draft = self.settings.clone(),
+            Scene::Playing => self.game_started = true,
+            _ => {}
+        }
+        if from == Scene::Playing && scene != Scene::Playing {
+            self.autosave();
+        }
+    }
+
+    fn apply_settings(&mut self, settings: Settings) {
+        i18n::set_language(settings.language);
+        self.settings = settings;
+    }
+
+    // Passa a usar as configurações e grava o arquivo
+    pub fn save_settings(&mut self, settings: Settings) {
+        self.apply_settings(settings);
+        match write_settings(&self.settings) {
+            Ok(()) => self.notify(Notice::SettingsSaved),
+            Err(e) => self.notify(Notice::SaveFailed(e)),
+        }
+        self.settings_watch.sync(&settings_path());
+    }
+
+    // Recarrega o arquivo de configurações quando ele muda no disco. Arquivo com erro
+    // não vale: as configurações em uso ficam e o erro aparece na tela.
+    pub fn watch_settings(&mut self) {
+        if !self.settings_watch.changed(&settings_path()) { return; }
+        match read_settings() {
+            Some(Ok(settings)) => {
+                self.settings_draft = settings.clone();
+                self.apply_settings(settings);
+                self.notify(Notice::SettingsReloaded);
+            }
+            Some(Err(e)) => self.notify(Notice::SettingsFailed(e)),
+            None => {}
+        }
+    }
+
+    // update é chamado em cada frame para lógica do jogo que não é input ou renderização
+    pub fn update(&mut self) {
+        if let Some(view) = self.replay.as_mut() {
+            view.player.update(&mut self.game);
+            self.collect_notices();
+            return;
+        }
+        self.game.update(get_frame_time());
+        let moving = matches!(self.game.game_state(), GameState::Shooting | GameState::BallsMoving);
+        if self.was_moving && !moving {
+            self.autosave();
+        }
+        self.was_moving = moving;
+
+        let rack_over = matches!(self.game.game_state(), GameState::GameOver | GameState::MatchOver);
+        if rack_over && !self.was_rack_over {
+            if let Some(replay) = Replay::of_rack(&self.game)
+                && let Err(e) = write_replay(&replay)
+            {
+                warn!("Falha ao gravar o replay: {}", e);
+            }
+            self.autosave();
+        }
+        self.was_rack_over = rack_over;
+        self.collect_notices();
+    }
+
+    pub fn notify(&mut self, notice: Notice) {
+        self.pending_notices.push(notice);
+    }
+
+    // Junta os avisos do app e os do jogo chegados neste quadro. Quando há algum,
+    // eles tomam o lugar dos que estavam na tela, na ordem em que aconteceram.
+    // Transição recusada é erro de regra e vai só para o log.
+    pub fn collect_notices(&mut self) {
+        for notification in self.game.take_notifications() {
+            if let Notification::InvalidTransition { from, to } = notification {
+                warn!("Transição de estado inválida: {:?} -> {:?}", from, to);
+                continue;
+            }
+            self.pending_notices.push(Notice::Game(notification));
+        }
+        if !self.pending_notices.is_empty() {
+            self.notices = std::mem::take(&mut self.pending_notices);
+        }
+    }
+
+    // Salva a partida a cada jogada resolvida, a cada ação do jogador e ao fechar.
+    // No Android o app pode ser encerrado em segundo plano sem aviso, então o save
+    // precisa estar sempre em dia em vez de depender do evento de saída.
+    // Durante o replay o que vale é a partida ao vivo guardada. Sem partida começada
+    // não há o que salvar, e a partida terminada apaga o save.
+    pub fn autosave(&mut self) {
+        if !self.game_started { return; }
+        let game = self.replay.as_ref().map_or(&self.game, |view| &view.live_game);
+        let result = if game.game_state() == GameState::MatchOver { remove_save() } else { write_save(game) };
+        if let Err(e) = result {
+            warn!("Falha ao salvar a partida: {}", e);
+        }
+    }
+
+    // render é chamado para desenhar tudo
+    pub fn render(&self) {
+        self.draw_game_elements(); // Chama a função de drawing.rs
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use macroquad::prelude::*;
+
+use potshot_core::script::ScriptedVariant;
+use potshot_core::table::TableSpec;
+use crate::tr;
+use crate::notices::Notice;
+use crate::labels::Label;
+
+// Fonte embutida no binário: o jogo abre de qualquer diretório, mesmo sem a pasta assets
+const EMBEDDED_FONT: &[u8] = include_bytes!("../assets/Inter-Regular.ttf");
+const FONT_FILE: &str = "Inter-Regular.ttf";
+// Variantes com script que vêm com o jogo, pelo nome do arquivo sem a extensão
+const EMBEDDED_VARIANTS: [(&str, &str); 1] = [
+    ("eight_ball", include_str!("../assets/variants/eight_ball.rhai")),
+];
+const VARIANTS_DIR: &str = "variants";
+const TABLES_DIR: &str = "tables";
+
+// Diretórios de assets, do que vale mais para o que vale menos: os das configurações,
+// o assets/ ao lado do executável e o assets/ do diretório atual. Cada um pode trazer
+// só parte dos arquivos; o que não estiver em nenhum vem do que está embutido.
+// Diretório das configurações que não existe é avisado, já que alguém o pediu.
+pub fn asset_dirs(extra: &[PathBuf], notices: &mut Vec<Notice>) -> Vec<PathBuf> {
+    for dir in extra.iter().filter(|dir| !dir.is_dir()) {
+        notices.push(Notice::AssetDirMissing(dir.display().to_string()));
+    }
+    let mut dirs = extra.to_vec();
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.join("assets"))) {
+        dirs.push(exe_dir);
+    }
+    dirs.push(PathBuf::from("assets"));
+    dirs
+}
+
+// Fonte da primeira camada que tiver o arquivo. Arquivo que existe mas não abre vira
+// aviso na tela e a busca segue; sem fonte nenhuma fica a padrão do macroquad (None).
+pub async fn load_font(dirs: &[PathBuf], notices: &mut Vec<Notice>) -> Option<Font> {
+    for dir in dirs {
+        let path = dir.join(FONT_FILE);
+        let Ok(bytes) = load_file(&path.to_string_lossy()).await else { continue; };
+        match load_ttf_font_from_bytes(&bytes) {
+            Ok(font) => return Some(font),
+            Err(e) => notices.push(Notice::AssetFailed { path: path.display().to_string(), error: e.to_string() }),
+        }
+    }
+    match load_ttf_font_from_bytes(EMBEDDED_FONT) {
+        Ok(font) => Some(font),
+        Err(e) => {
+            notices.push(Notice::AssetFailed { path: tr!("assets.embedded", file = FONT_FILE), error: e.to_string() });
+            None
+        }
+    }
+}
+
+// Variantes com script: os .rhai de variants/ em cada camada, por cima das embutidas.
+// Arquivo com o mesmo nome numa camada de cima esconde o de baixo. Script que não
+// compila vira aviso e fica de fora; a lista sai em ordem de nome.
+pub fn load_variants(dirs: &[PathBuf], notices: &mut Vec<Notice>) -> Vec<ScriptedVariant> {
+    let mut sources: BTreeMap<String, (String, String)> = EMBEDDED_VARIANTS.iter()
+        .map(|&(name, source)| (name.to_string(), (tr!("assets.embedded", file = name), source.to_string())))
+        .collect();
+    for dir in dirs.iter().rev() {
+        let Ok(entries) = std::fs::read_dir(dir.join(VARIANTS_DIR)) else { continue; };
+        for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            if path.extension().is_none_or(|ext| ext != "rhai") { continue; }
+            let Some(name) = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()) else { continue; };
+            match std::fs::read_to_string(&path) {
+                Ok(source) => { sources.insert(name, (path.display().to_string(), source)); }
+                Err(e) => notices.push(Notice::ScriptFailed { path: path.display().to_string(), error: e.to_string() }),
+            }
+        }
+    }
+    sources.into_iter()
+        .filter_map(|(name, (path, source))| match ScriptedVariant::new(&name, &source) {
+            Ok(variant) => Some(variant),
+            Err(error) => {
+                notices.push(Notice::ScriptFailed { path, error: error.label() });
+                None
+            }
+        })
+        .collect()
+}
+
+// Mesas: as embutidas no núcleo e os .txt de tables/ em cada camada. Vale o `name`
+// de dentro do arquivo, então uma mesa com o nome de uma embutida a substitui; as
+// novas entram depois das embutidas, em ordem de nome.
+pub fn load_tables(dirs: &[PathBuf], notices: &mut Vec<Notice>) -> Vec<TableSpec> {
+    let mut tables = TableSpec::builtin().to_vec();
+    let mut extra: BTreeMap<String, TableSpec> = BTreeMap::new();
+    for dir in dirs.iter().rev() {
+        let Ok(entries) = std::fs::read_dir(dir.join(TABLES_DIR)) else { continue; };
+        let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+        paths.sort();
+        for path in paths.into_iter().filter(|path| path.extension().is_some_and(|ext| ext == "txt")) {
+            let table = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| TableSpec::parse(&source).map_err(|e| e.label()));
+            match table {
+                Ok(table) => match tables.iter_mut().find(|builtin| builtin.name == table.name) {
+                    Some(builtin) => *builtin = table,
+                    None => { extra.insert(table.name.clone(), table); }
+                },
+                Err(error) => notices.push(Notice::TableFailed { path: path.display().to_string(), error }),
+            }
+        }
+    }
+    tables.extend(extra.into_values());
+    tables
+}
@@ -2,20 +2,12 @@ use macroquad::{
     color::{BLACK, WHITE},
     prelude::Color,
 };
+use crate::tr;
 
-// Renomeado para ficar mais genérico, mas pode manter ball_colors se preferir
+// Cores da mesa e da interface; as das bolas vêm do núcleo (potshot_core::colors)
 pub mod game_colors {
     use super::*; // Importa Color, BLACK, WHITE do escopo pai (colors.rs)
 
-    pub const CUE: Color = WHITE;
-    pub const SOLID_YELLOW: Color = Color::new(0.98, 0.80, 0.08, 1.0); // #facc15
-    pub const SOLID_BLUE: Color = Color::new(0.23, 0.51, 0.96, 1.0); // #3b82f6
-    pub const SOLID_RED: Color = Color::new(0.94, 0.27, 0.27, 1.0); // #ef4444
-    pub const SOLID_PURPLE: Color = Color::new(0.66, 0.33, 0.97, 1.0); // #a855f7
-    pub const SOLID_ORANGE: Color = Color::new(0.98, 0.45, 0.09, 1.0); // #f97316
-    pub const SOLID_GREEN: Color = Color::new(0.13, 0.77, 0.35, 1.0); // #22c55e
-    pub const SOLID_MAROON: Color = Color::new(0.53, 0.07, 0.21, 1.0); // #881337
-    pub const EIGHT_BALL: Color = Color::new(0.09, 0.09, 0.09, 1.0); // #171717
     pub const STRIPE_PRIMARY: Color = WHITE;
     pub const POCKET_CENTER: Color = Color::new(0.06, 0.06, 0.06, 1.0);
     pub const POCKET_BORDER: Color = BLACK;
@@ -30,6 +22,87 @@ pub mod game_colors {
     pub const BUTTON_HOVER_BG: Color = Color::new(0.14, 0.39, 0.92, 1.0); // blue-600
     pub const UI_BG_COLOR: Color = Color::new(0.067, 0.094, 0.153, 0.95); // Tailwind gray-900 (#111827) com alpha
     pub const HUD_TEXT_COLOR: Color = WHITE;
+    pub const OVERLAY_BG: Color = Color::new(0.0, 0.0, 0.0, 0.7);
+    pub const CLOCK_WARNING: Color = Color::new(0.94, 0.27, 0.27, 1.0); // red-500
+    pub const CAROM_TABLE_BG: Color = Color::new(0.10, 0.35, 0.70, 1.0);
+    pub const SNOOKER_TABLE_BG: Color = Color::new(0.0, 0.42, 0.18, 1.0);
+    pub const TABLE_MARKING: Color = Color::new(1.0, 1.0, 1.0, 0.15);
+    // Sombra da borracha por cima da cor da borda, então vale em qualquer tema
+    pub const CUSHION: Color = Color::new(0.0, 0.0, 0.0, 0.25);
+    pub const DIAMOND: Color = Color::new(0.96, 0.93, 0.84, 1.0);
     pub const HUD_POCKETED_BALL_BG: Color = Color::new(0.2, 0.2, 0.2, 0.5);
 }
 
+// Tema escolhido nas configurações: troca o pano, a borda da mesa e os botões.
+// O resto da interface usa as cores fixas de game_colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Tournament,
+    Night,
+}
+
+pub struct Palette {
+    pub table_bg: Color,
+    pub snooker_table_bg: Color,
+    pub carom_table_bg: Color,
+    pub table_border: Color,
+    pub button_bg: Color,
+    pub button_hover_bg: Color,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Classic, Theme::Tournament, Theme::Night];
+
+    pub fn next(self) -> Theme {
+        let idx = Self::ALL.iter().position(|&theme| theme == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Classic => Palette {
+                table_bg: game_colors::TABLE_BG,
+                snooker_table_bg: game_colors::SNOOKER_TABLE_BG,
+                carom_table_bg: game_colors::CAROM_TABLE_BG,
+                table_border: game_colors::TABLE_BORDER_COLOR,
+                button_bg: game_colors::BUTTON_BG,
+                button_hover_bg: game_colors::BUTTON_HOVER_BG,
+            },
+            // Pano azul de torneio e madeira escura
+            Theme::Tournament => Palette {
+                table_bg: Color::new(0.05, 0.36, 0.66, 1.0),
+                snooker_table_bg: game_colors::SNOOKER_TABLE_BG,
+                carom_table_bg: Color::new(0.05, 0.28, 0.55, 1.0),
+                table_border: Color::new(0.16, 0.10, 0.07, 1.0),
+                button_bg: Color::new(0.28, 0.33, 0.41, 1.0), // slate-600
+                button_hover_bg: Color::new(0.20, 0.25, 0.33, 1.0), // slate-700
+            },
+            // Cores mais escuras para jogar à noite
+            Theme::Night => Palette {
+                table_bg: Color::new(0.02, 0.30, 0.22, 1.0),
+                snooker_table_bg: Color::new(0.0, 0.24, 0.11, 1.0),
+                carom_table_bg: Color::new(0.06, 0.20, 0.40, 1.0),
+                table_border: Color::new(0.18, 0.09, 0.04, 1.0),
+                button_bg: Color::new(0.71, 0.33, 0.04, 1.0), // amber-700
+                button_hover_bg: Color::new(0.57, 0.25, 0.05, 1.0), // amber-800
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::Classic => write!(f, "{}", tr!("theme.classic")),
+            Theme::Tournament => write!(f, "{}", tr!("theme.tournament")),
+            Theme::Night => write!(f, "{}", tr!("theme.night")),
+        }
+    }
+}
+
+// Converte a cor do núcleo para a do macroquad
+pub fn to_color(color: potshot_core::colors::Color) -> Color {
+    Color::new(color.r, color.g, color.b, color.a)
+}
@@ -1,7 +0,0 @@
-// Tamanhos que não dependem da tela inicialmente
-pub const POCKET_RADIUS_MULTIPLIER: f32 = 1.8;
-pub const FRICTION: f32 = 0.98;
-pub const MIN_SPEED: f32 = 0.05;
-pub const CUSHION_ELASTICITY: f32 = 0.8;
-pub const BALL_ELASTICITY: f32 = 0.95;
-
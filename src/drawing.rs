@@ -1,80 +1,195 @@
 use macroquad::prelude::*;
-use crate::Game;
+use crate::app::App;
 use crate::colors::game_colors;
+use crate::colors::to_color;
+use potshot_core::ball::Ball;
+use potshot_core::pocket::Pocket;
+use potshot_core::types::{GameState, GameVariant, PlacementArea, PlayerGroup, PlayerId};
+use potshot_core::config::BallDefinition;
+use potshot_core::clock::format_clock;
+use potshot_core::constants::SHOT_CLOCK_WARNING_SECONDS;
+use crate::i18n;
+use crate::tr;
+use crate::labels::{short_label, snooker_phase_label, Label};
 
-impl Game {
+impl App {
     pub fn draw_game_elements(&self) { // Renomeado para evitar conflito com draw em game.rs
         clear_background(Color::new(0.05,0.05,0.07,1.0));
 
         self.draw_table_and_elements();
         self.draw_hud();
+        if self.replay.is_some() {
+            self.draw_main_message();
+            return;
+        }
+        self.draw_shot_clock();
+        self.draw_undo_buttons();
         self.draw_main_message();
     }
 
     fn draw_table_and_elements(&self) {
+        let palette = self.settings.theme.palette();
+        let table_size = self.table_size();
+        let ball_radius = self.game.ball_radius * self.scale;
         draw_rectangle(
             self.table_offset.x,
             self.table_offset.y,
-            self.table_width + self.table_border_thickness * 2.0,
-            self.table_height + self.table_border_thickness * 2.0,
-            game_colors::TABLE_BORDER_COLOR,
+            table_size.x + self.table_border_thickness * 2.0,
+            table_size.y + self.table_border_thickness * 2.0,
+            palette.table_border,
         );
         draw_rectangle(
             self.game_area_offset.x,
             self.game_area_offset.y,
-            self.table_width,
-            self.table_height,
-            game_colors::TABLE_BG,
+            table_size.x,
+            table_size.y,
+            match self.game.variant {
+                GameVariant::Snooker => palette.snooker_table_bg,
+                GameVariant::ThreeCushion => palette.carom_table_bg,
+                _ => palette.table_bg,
+            },
         );
 
-        let baulk_line_x = self.game_area_offset.x + self.table_width * 0.25;
-        draw_line(
-            baulk_line_x, self.game_area_offset.y,
-            baulk_line_x, self.game_area_offset.y + self.table_height,
-            1.0, Color::new(1.0, 1.0, 1.0, 0.15),
-        );
-        draw_circle(
-            baulk_line_x, self.game_area_offset.y + self.table_height / 2.0,
-            self.ball_radius / 3.0, Color::new(1.0, 1.0, 1.0, 0.2),
+        // Borracha das tabelas por dentro da madeira e os diamantes no meio da madeira
+        let spec = &self.game.table;
+        let mm = self.game.table_scale() * self.scale;
+        let cushion = (spec.cushion.width * mm).min(self.table_border_thickness * 0.6);
+        draw_rectangle_lines(
+            self.game_area_offset.x - cushion, self.game_area_offset.y - cushion,
+            table_size.x + cushion * 2.0, table_size.y + cushion * 2.0,
+            cushion * 2.0, game_colors::CUSHION,
         );
-        let head_spot_x = self.game_area_offset.x + self.table_width * 0.7;
-        let head_spot_y = self.game_area_offset.y + self.table_height / 2.0;
-        draw_circle(head_spot_x, head_spot_y, self.ball_radius / 3.0, Color::new(1.0,1.0,1.0,0.1));
+        let rail = cushion + (self.table_border_thickness - cushion) / 2.0;
+        let (long, short) = spec.diamonds;
+        for i in 1..long {
+            let x = self.game_area_offset.x + table_size.x * i as f32 / long as f32;
+            for y in [self.game_area_offset.y - rail, self.game_area_offset.y + table_size.y + rail] {
+                draw_circle(x, y, ball_radius / 4.0, game_colors::DIAMOND);
+            }
+        }
+        for i in 1..short {
+            let y = self.game_area_offset.y + table_size.y * i as f32 / short as f32;
+            for x in [self.game_area_offset.x - rail, self.game_area_offset.x + table_size.x + rail] {
+                draw_circle(x, y, ball_radius / 4.0, game_colors::DIAMOND);
+            }
+        }
+
+        for &line in &spec.lines {
+            let x = self.game_area_offset.x + line * mm;
+            draw_line(x, self.game_area_offset.y, x, self.game_area_offset.y + table_size.y, 1.0, game_colors::TABLE_MARKING);
+        }
+        if spec.d_radius > 0.0 {
+            let head_spot = self.to_screen(self.game.head_spot());
+            draw_arc(head_spot.x, head_spot.y, 48, self.game.snooker_d_radius() * self.scale, 90.0, 1.0, 180.0, game_colors::TABLE_MARKING);
+        }
+        for &(_, pos) in &spec.spots {
+            let spot = self.game_area_offset + pos * mm;
+            draw_circle(spot.x, spot.y, ball_radius / 4.0, game_colors::TABLE_MARKING);
+        }
 
-        for pocket in &self.pockets {
-            pocket.draw(self.game_area_offset);
+        for pocket in &self.game.pockets {
+            self.draw_pocket(pocket);
         }
-        for ball in &self.balls {
-            ball.draw(self.font.clone(), self.game_area_offset);
+        // Caçapa cantada para a 8
+        if let Some(pocket) = self.game.called_pocket.and_then(|idx| self.game.pockets.get(idx)) {
+            let center = self.to_screen(pocket.pos);
+            draw_circle_lines(center.x, center.y, pocket.radius * self.scale * 1.2, 3.0, palette.button_bg);
+        }
+        for ball in &self.game.balls {
+            self.draw_ball(ball);
+        }
+
+        if let Some(cb) = self.game.cue_ball_idx.and_then(|idx| self.game.balls.get(idx))
+            && !cb.in_pocket && self.game.game_state() == GameState::Aiming && self.replay.is_none()
+        {
+            let cue_ball_abs_pos = self.to_screen(cb.pos);
+            let aim_line_length = table_size.x * self.settings.aim_assist.line_fraction();
+            self.cue.draw(cue_ball_abs_pos, cb.radius * self.scale, aim_line_length, self.cue_max_length, self.cue_width);
         }
 
-        if let Some(cb_idx) = self.cue_ball_idx {
-            if let Some(cb) = self.balls.get(cb_idx) {
-                if !cb.in_pocket && self.cue.visible && self.game_state != crate::types::GameState::GameOver &&
-                   self.game_state != crate::types::GameState::Initializing && self.game_state != crate::types::GameState::RepositionCueBall {
-                    let cue_ball_abs_pos = cb.pos + self.game_area_offset;
-                    self.cue.draw(cue_ball_abs_pos, cb.radius, self.table_width, self.cue_max_length, self.cue_width);
+        if self.game.game_state() == GameState::RepositionCueBall {
+            match self.game.cue_ball_placement_area() {
+                PlacementArea::Kitchen => {
+                    let reposition_area_width = self.game.baulk_line_x() * self.scale;
+                    draw_rectangle(
+                        self.game_area_offset.x, self.game_area_offset.y,
+                        reposition_area_width, table_size.y,
+                        game_colors::REPOSITION_AREA_FILL,
+                    );
+                    draw_rectangle_lines(
+                        self.game_area_offset.x + 1.0, self.game_area_offset.y + 1.0,
+                        reposition_area_width - 2.0, table_size.y - 2.0,
+                        2.0, game_colors::REPOSITION_AREA_STROKE,
+                    );
                 }
+                PlacementArea::D => {
+                    let head_spot = self.to_screen(self.game.head_spot());
+                    let d_radius = self.game.snooker_d_radius() * self.scale;
+                    draw_arc(head_spot.x, head_spot.y, 48, 0.0, 90.0, d_radius, 180.0, game_colors::REPOSITION_AREA_FILL);
+                    draw_arc(head_spot.x, head_spot.y, 48, d_radius, 90.0, 2.0, 180.0, game_colors::REPOSITION_AREA_STROKE);
+                }
+                PlacementArea::Anywhere => {} // A mesa inteira vale, não há área para destacar
+            }
+            if self.game.is_in_cue_ball_area(self.to_table(self.input_state.current_pos)) {
+                draw_circle(self.input_state.current_pos.x, self.input_state.current_pos.y, ball_radius, Color::new(1.0,1.0,1.0,0.3));
             }
         }
+    }
 
-        if self.game_state == crate::types::GameState::RepositionCueBall {
-            let reposition_area_width = self.table_width * 0.25;
-            draw_rectangle(
-                self.game_area_offset.x, self.game_area_offset.y,
-                reposition_area_width, self.table_height,
-                game_colors::REPOSITION_AREA_FILL,
-            );
-            draw_rectangle_lines(
-                self.game_area_offset.x + 1.0, self.game_area_offset.y + 1.0,
-                reposition_area_width - 2.0, self.table_height - 2.0,
-                2.0, game_colors::REPOSITION_AREA_STROKE,
+    fn draw_pocket(&self, pocket: &Pocket) {
+        let draw_pos = self.to_screen(pocket.pos);
+        let radius = pocket.radius * self.scale;
+        draw_circle(draw_pos.x, draw_pos.y, radius, game_colors::POCKET_BORDER);
+        draw_circle(draw_pos.x, draw_pos.y, radius * 0.7, game_colors::POCKET_CENTER);
+    }
+
+    fn draw_ball(&self, ball: &Ball) {
+        if ball.in_pocket {
+            return;
+        }
+        let draw_pos = self.to_screen(ball.pos);
+        let radius = ball.radius * self.scale;
+        let color = to_color(ball.color);
+
+        draw_circle(draw_pos.x, draw_pos.y, radius, color);
+
+        let highlight_offset = radius * 0.4;
+        draw_circle(
+            draw_pos.x - highlight_offset * 0.7,
+            draw_pos.y - highlight_offset * 0.7,
+            radius * 0.3,
+            Color::new(1.0, 1.0, 1.0, 0.6),
+        );
+
+        if ball.number != 0 && ball.show_number { // Not cue ball
+            let text_x = draw_pos.x;
+            let text_y = draw_pos.y;
+            let circle_radius_for_number = radius * 0.55;
+
+            if ball.is_striped {
+                draw_circle(text_x, text_y, radius, game_colors::STRIPE_PRIMARY);
+                draw_circle(text_x, text_y, radius * 0.6, color);
+                draw_circle(text_x, text_y, circle_radius_for_number, game_colors::STRIPE_PRIMARY);
+            } else { // Solid or 8-ball
+                draw_circle(text_x, text_y, circle_radius_for_number, WHITE);
+            }
+
+            let text_color = if ball.number == 8 || !ball.is_striped { BLACK } else { color };
+            let text = ball.number.to_string();
+            let font_size = (radius * 1.1) as u16;
+            let text_params = TextParams {
+                font: self.font.as_ref(),
+                font_size,
+                color: text_color,
+                ..Default::default()
+            };
+            let text_dimensions = measure_text(&text, self.font.as_ref(), font_size, 1.0);
+            draw_text_ex(
+                &text,
+                text_x - text_dimensions.width / 2.0,
+                text_y + text_dimensions.offset_y / 2.0 + text_dimensions.height * 0.3,
+                text_params,
             );
-            let input_table_relative_pos = self.input_state.current_pos - self.game_area_offset;
-             if input_table_relative_pos.x > 0.0 && input_table_relative_pos.x < reposition_area_width &&
-                input_table_relative_pos.y > 0.0 && input_table_relative_pos.y < self.table_height {
-                draw_circle(self.input_state.current_pos.x, self.input_state.current_pos.y, self.ball_radius, Color::new(1.0,1.0,1.0,0.3));
-             }
         }
     }
 
@@ -85,66 +200,297 @@ impl Game {
         let hud_height = screen_h * 0.15;
         let padding = screen_h * 0.015;
         let text_font_size = (screen_h * 0.025).max(16.0) as u16;
-        let ball_display_radius = (self.ball_radius * 0.7).max(5.0);
+        let ball_display_radius = (self.game.ball_radius * self.scale * 0.7).max(5.0);
 
         draw_rectangle(0.0, hud_y_start, screen_w, hud_height, game_colors::UI_BG_COLOR);
 
-        let p1_text = format!("P1: {}", self.player1_group);
+        if self.game.players.len() > 2 {
+            self.draw_player_columns(hud_y_start, padding, text_font_size);
+            self.draw_buttons();
+            return;
+        }
+
+        let p1_text = self.player_hud_label(PlayerId(0));
         draw_text_ex(&p1_text, padding, hud_y_start + padding + text_font_size as f32 * 0.5, TextParams {
-            font: Some(&self.font), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
+            font: self.font.as_ref(), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
         });
         let p1_balls_y = hud_y_start + padding + text_font_size as f32 + padding * 0.5;
-        for (i, ball_def) in self.player1_pocketed_balls.iter().enumerate() {
+        // No contínuo as bolas voltam para a mesa, então o placar substitui as listas
+        let (p1_pocketed, p2_pocketed): (&[BallDefinition], &[BallDefinition]) = match self.game.variant {
+            GameVariant::EightBall | GameVariant::Blackball | GameVariant::Scripted => (&self.game.players[0].pocketed_balls, &self.game.players[1].pocketed_balls),
+            _ => (&[], &[]),
+        };
+        for (i, ball_def) in p1_pocketed.iter().enumerate() {
             let x = padding + i as f32 * (ball_display_radius * 2.5);
-            draw_circle(x + ball_display_radius, p1_balls_y + ball_display_radius, ball_display_radius, ball_def.color);
+            draw_circle(x + ball_display_radius, p1_balls_y + ball_display_radius, ball_display_radius, to_color(ball_def.color));
             if ball_def.is_striped {
                 draw_circle(x + ball_display_radius, p1_balls_y + ball_display_radius, ball_display_radius * 0.6, game_colors::STRIPE_PRIMARY);
-                draw_circle(x + ball_display_radius, p1_balls_y + ball_display_radius, ball_display_radius * 0.3, ball_def.color);
+                draw_circle(x + ball_display_radius, p1_balls_y + ball_display_radius, ball_display_radius * 0.3, to_color(ball_def.color));
             }
         }
 
-        let p2_text = format!("P2: {}", self.player2_group);
-        let p2_text_dims = measure_text(&p2_text, Some(&self.font), text_font_size, 1.0);
+        let p2_text = self.player_hud_label(PlayerId(1));
+        let p2_text_dims = measure_text(&p2_text, self.font.as_ref(), text_font_size, 1.0);
         draw_text_ex(&p2_text, screen_w - padding - p2_text_dims.width, hud_y_start + padding + text_font_size as f32 * 0.5, TextParams {
-            font: Some(&self.font), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
+            font: self.font.as_ref(), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
         });
         let p2_balls_y = hud_y_start + padding + text_font_size as f32 + padding * 0.5;
-        for (i, ball_def) in self.player2_pocketed_balls.iter().enumerate() {
-            let x = screen_w - padding - (self.player2_pocketed_balls.len() as f32 - i as f32) * (ball_display_radius * 2.5);
-             draw_circle(x + ball_display_radius, p2_balls_y + ball_display_radius, ball_display_radius, ball_def.color);
+        for (i, ball_def) in p2_pocketed.iter().enumerate() {
+            let x = screen_w - padding - (p2_pocketed.len() as f32 - i as f32) * (ball_display_radius * 2.5);
+             draw_circle(x + ball_display_radius, p2_balls_y + ball_display_radius, ball_display_radius, to_color(ball_def.color));
             if ball_def.is_striped {
                 draw_circle(x + ball_display_radius, p2_balls_y + ball_display_radius, ball_display_radius * 0.6, game_colors::STRIPE_PRIMARY);
-                draw_circle(x + ball_display_radius, p2_balls_y + ball_display_radius, ball_display_radius * 0.3, ball_def.color);
+                draw_circle(x + ball_display_radius, p2_balls_y + ball_display_radius, ball_display_radius * 0.3, to_color(ball_def.color));
             }
         }
         
-        let turn_text = format!("Vez de: {}", self.current_player);
-        let turn_text_dims = measure_text(&turn_text, Some(&self.font), text_font_size, 1.0);
+        let turn_text = self.turn_text();
+        let turn_text_dims = measure_text(&turn_text, self.font.as_ref(), text_font_size, 1.0);
         let turn_text_y = hud_y_start + padding + text_font_size as f32 * 0.5;
         draw_text_ex(&turn_text, screen_w / 2.0 - turn_text_dims.width / 2.0, turn_text_y, TextParams {
-            font: Some(&self.font), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
+            font: self.font.as_ref(), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
+        });
+
+        if self.replay.is_some() {
+            self.draw_buttons();
+            return;
+        }
+        if self.game.variant.uses_called_ball() && self.game.game_state() == GameState::Aiming {
+            self.draw_call_picker();
+        }
+
+        self.draw_buttons();
+        if self.game.game_state() == GameState::BreakChoice {
+            self.draw_button(self.choice_left_button_rect, &tr!("ui.rerack"));
+            self.draw_button(self.choice_right_button_rect, &tr!("ui.accept_table"));
+        }
+        if self.game.game_state() == GameState::GroupChoice {
+            self.draw_button(self.choice_left_button_rect, &PlayerGroup::Solids.label());
+            self.draw_button(self.choice_right_button_rect, &PlayerGroup::Stripes.label());
+        }
+        if self.game.game_state() == GameState::GameOver {
+            self.draw_button(self.center_button_rect, &tr!("ui.next_rack"));
+            self.draw_button(self.replay_button_rect, &tr!("ui.watch_replay"));
+        }
+    }
+
+    fn player_hud_label(&self, id: PlayerId) -> String {
+        let player = self.game.player(id);
+        // Em corrida os racks ganhos aparecem entre colchetes
+        let label = if self.game.match_settings.is_single_rack() {
+            short_label(id)
+        } else {
+            format!("{} [{}]", short_label(id), self.game.match_score[id.0])
+        };
+        let text = match self.game.variant {
+            GameVariant::StraightPool => tr!("hud.points", label = label, score = player.score, fouls = player.consecutive_fouls),
+            GameVariant::Snooker => format!("{}: {}", label, player.score),
+            GameVariant::ThreeCushion => {
+                let ball = if player.cue_ball == 0 { tr!("hud.carom_white") } else { tr!("hud.carom_yellow") };
+                format!("{} ({}): {}", label, ball, player.score)
+            }
+            GameVariant::Cutthroat if player.eliminated => tr!("hud.cutthroat_out", label = label, group = player.group.label()),
+            GameVariant::Cutthroat => tr!(
+                "hud.cutthroat",
+                label = label, group = player.group.label(), count = self.game.balls_left_in_group(player.group), fouls = self.foul_suffix(id),
+            ),
+            GameVariant::Scripted => tr!(
+                "hud.scripted",
+                label = label, group = player.group.label(), score = player.score, fouls = self.foul_suffix(id),
+            ),
+            _ => format!("{}: {}{}", label, player.group.label(), self.foul_suffix(id)),
+        };
+        match player.match_time_left {
+            Some(time_left) if !player.eliminated => format!("{} · {}", text, format_clock(time_left)),
+            _ => text,
+        }
+    }
+
+    // Contagem da tacada no canto superior esquerdo, com o botão de extensão à direita
+    fn draw_shot_clock(&self) {
+        if self.game.time_control.shot_seconds.is_none() || !self.game.clock_is_running() { return; }
+        let font_size = (screen_height() * 0.035).max(18.0) as u16;
+        let text = tr!("hud.shot_clock", seconds = self.game.shot_time_left.max(0.0).ceil());
+        let color = if self.game.shot_time_left <= SHOT_CLOCK_WARNING_SECONDS { game_colors::CLOCK_WARNING } else { game_colors::HUD_TEXT_COLOR };
+        // À esquerda do botão de extensão, longe do desfazer e da semente no outro canto
+        let dims = measure_text(&text, self.font.as_ref(), font_size, 1.0);
+        let x = self.extension_button_rect.x - dims.width - screen_width() * 0.01;
+        draw_text_ex(&text, x, self.extension_button_rect.y + self.extension_button_rect.h * 0.75, TextParams {
+            font: self.font.as_ref(), font_size, color, ..Default::default()
+        });
+        if self.game.can_use_extension() {
+            self.draw_button(self.extension_button_rect, &tr!("ui.extension", count = self.game.current().extensions_left));
+        }
+    }
+
+    // Desfazer e refazer aparecem só quando há o que desfazer ou refazer
+    fn draw_undo_buttons(&self) {
+        if !self.game.undo_allowed() { return; }
+        if self.history.can_undo(&self.game) {
+            self.draw_button(self.undo_button_rect, &tr!("ui.undo"));
+        }
+        if self.history.can_redo(&self.game) {
+            self.draw_button(self.redo_button_rect, &tr!("ui.redo"));
+        }
+    }
+
+    // Faltas seguidas aparecem no HUD quando a variante usa a regra das três faltas
+    fn foul_suffix(&self, id: PlayerId) -> String {
+        let fouls = self.game.player(id).consecutive_fouls;
+        if self.game.three_foul_rule() && fouls > 0 {
+            format!(" {}", tr!("hud.fouls", count = fouls))
+        } else {
+            String::new()
+        }
+    }
+
+    fn turn_text(&self) -> String {
+        if let Some(view) = &self.replay {
+            let total = view.player.replay.shot_count();
+            return tr!("hud.replay", shot = view.player.shots_taken().min(total), total = total, player = self.game.current_player.label());
+        }
+        if self.game.is_lagging() {
+            return tr!("hud.lag", player = self.game.current_player.label());
+        }
+        match self.game.variant {
+            GameVariant::StraightPool | GameVariant::ThreeCushion => {
+                tr!("hud.turn_target", player = self.game.current_player.label(), target = self.game.target_score)
+            }
+            GameVariant::Snooker => tr!(
+                "hud.turn_snooker",
+                player = self.game.current_player.label(), ball = snooker_phase_label(&self.game), points = self.game.snooker_points_remaining(),
+            ),
+            GameVariant::EightBall if self.game.must_call_eight() && self.game.called_pocket.is_none() => {
+                tr!("hud.turn_call_pocket", player = self.game.current_player.label())
+            }
+            GameVariant::Blackball if self.game.free_shot => tr!("hud.turn_free_shot", player = self.game.current_player.label()),
+            GameVariant::Blackball if self.game.extra_visit => tr!("hud.turn_two_shots", player = self.game.current_player.label()),
+            _ => tr!("hud.turn", player = self.game.current_player.label()),
+        }
+    }
+
+    // Com mais de dois jogadores cada um ganha uma coluna e a vez vai na linha de baixo
+    fn draw_player_columns(&self, hud_y_start: f32, padding: f32, text_font_size: u16) {
+        let screen_w = screen_width();
+        let column_w = screen_w / self.game.players.len() as f32;
+        let label_y = hud_y_start + padding + text_font_size as f32 * 0.5;
+        for id in self.game.player_ids() {
+            let text = self.player_hud_label(id);
+            let dims = measure_text(&text, self.font.as_ref(), text_font_size, 1.0);
+            let x = column_w * id.0 as f32 + (column_w - dims.width) / 2.0;
+            if id == self.game.current_player && !matches!(self.game.game_state(), GameState::GameOver | GameState::MatchOver) {
+                draw_rectangle(x - padding * 0.5, label_y - dims.offset_y - padding * 0.3, dims.width + padding, dims.height + padding * 0.6, self.settings.theme.palette().button_bg);
+            }
+            let color = if self.game.player(id).eliminated { game_colors::HUD_POCKETED_BALL_BG } else { game_colors::HUD_TEXT_COLOR };
+            draw_text_ex(&text, x, label_y, TextParams {
+                font: self.font.as_ref(), font_size: text_font_size, color, ..Default::default()
+            });
+        }
+
+        let turn_text = self.turn_text();
+        let dims = measure_text(&turn_text, self.font.as_ref(), text_font_size, 1.0);
+        draw_text_ex(&turn_text, screen_w / 2.0 - dims.width / 2.0, label_y + text_font_size as f32 + padding * 0.5, TextParams {
+            font: self.font.as_ref(), font_size: text_font_size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
         });
+    }
+
+    fn draw_buttons(&self) {
+        if let Some(view) = &self.replay {
+            let [previous, play, next, speed, exit] = self.hud_row_rects;
+            self.draw_button(previous, &tr!("ui.replay_previous"));
+            self.draw_button(play, &if view.player.playing { tr!("ui.pause") } else { tr!("ui.play") });
+            self.draw_button(next, &tr!("ui.replay_next"));
+            self.draw_button(speed, &tr!("ui.replay_speed", speed = view.player.speed));
+            self.draw_button(exit, &tr!("ui.replay_exit"));
+            return;
+        }
+        // As opções da partida ficam na tela de nova partida; aqui só o menu
+        self.draw_button(self.pause_button_rect, &tr!("ui.menu"));
+        let seed_text = match self.game.rack_options.seed {
+            Some(seed) if self.seed_focus => tr!("ui.seed_editing", seed = seed),
+            Some(seed) => tr!("ui.seed_fixed", seed = seed),
+            None => tr!("ui.seed", seed = self.game.rack_seed),
+        };
+        self.draw_button(self.seed_button_rect, &seed_text);
+        self.draw_button(self.save_button_rect, &tr!("ui.save"));
+        self.draw_button(self.load_button_rect, &tr!("ui.load"));
+        self.draw_button(self.language_button_rect, &i18n::language().code().to_uppercase());
+    }
 
+    pub fn draw_button(&self, rect: Rect, text: &str) {
         let (mouse_x, mouse_y) = mouse_position();
-        let btn_color = if self.reset_button_rect.contains(vec2(mouse_x, mouse_y)) { game_colors::BUTTON_HOVER_BG } else { game_colors::BUTTON_BG };
-        draw_rectangle(self.reset_button_rect.x, self.reset_button_rect.y, self.reset_button_rect.w, self.reset_button_rect.h, btn_color);
-        let btn_text = "Reiniciar";
-        let btn_font_size = (self.reset_button_rect.h * 0.5).max(14.0) as u16;
-        let btn_text_dims = measure_text(btn_text, Some(&self.font), btn_font_size, 1.0);
-        draw_text_ex(btn_text,
-            self.reset_button_rect.x + (self.reset_button_rect.w - btn_text_dims.width) / 2.0,
-            self.reset_button_rect.y + (self.reset_button_rect.h - btn_text_dims.height) / 2.0 + btn_text_dims.offset_y * 0.8,
-            TextParams { font: Some(&self.font), font_size: btn_font_size, color: game_colors::BUTTON_TEXT, ..Default::default() });
+        let palette = self.settings.theme.palette();
+        let btn_color = if rect.contains(vec2(mouse_x, mouse_y)) { palette.button_hover_bg } else { palette.button_bg };
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, btn_color);
+        let btn_font_size = (rect.h * 0.5).max(14.0) as u16;
+        let btn_text_dims = measure_text(text, self.font.as_ref(), btn_font_size, 1.0);
+        draw_text_ex(text,
+            rect.x + (rect.w - btn_text_dims.width) / 2.0,
+            rect.y + (rect.h - btn_text_dims.height) / 2.0 + btn_text_dims.offset_y * 0.8,
+            TextParams { font: self.font.as_ref(), font_size: btn_font_size, color: game_colors::BUTTON_TEXT, ..Default::default() });
+    }
+
+    fn hud_ball_radius(&self) -> f32 {
+        (self.game.ball_radius * self.scale * 0.7).max(5.0)
+    }
+
+    // Fileira de bolas da mesa no centro do HUD, usada para cantar a bola da tacada
+    fn call_picker_layout(&self) -> Vec<(u8, Vec2)> {
+        let screen_h = screen_height();
+        let padding = screen_h * 0.015;
+        let text_font_size = (screen_h * 0.025).max(16.0) as u16;
+        let radius = self.hud_ball_radius();
+        let spacing = radius * 2.5;
+        let row_y = screen_h * 0.85 + padding + text_font_size as f32 + padding * 0.5 + radius;
+
+        let numbers = self.game.callable_balls();
+
+        let start_x = screen_width() / 2.0 - (numbers.len() as f32 - 1.0) * spacing / 2.0;
+        numbers.into_iter()
+            .enumerate()
+            .map(|(i, number)| (number, vec2(start_x + i as f32 * spacing, row_y)))
+            .collect()
+    }
+
+    pub fn call_picker_hit(&self, screen_pos: Vec2) -> Option<u8> {
+        if !self.game.variant.uses_called_ball() { return None; }
+        let radius = self.hud_ball_radius();
+        self.call_picker_layout()
+            .into_iter()
+            .find(|(_, center)| center.distance(screen_pos) < radius * 1.2)
+            .map(|(number, _)| number)
+    }
+
+    fn draw_call_picker(&self) {
+        let radius = self.hud_ball_radius();
+        for (number, center) in self.call_picker_layout() {
+            let Some(def) = self.game.ball_definitions_map.get(&number) else { continue; };
+            draw_circle(center.x, center.y, radius * 1.2, game_colors::HUD_POCKETED_BALL_BG);
+            draw_circle(center.x, center.y, radius, to_color(def.color));
+            if def.is_striped {
+                draw_circle(center.x, center.y, radius * 0.6, game_colors::STRIPE_PRIMARY);
+                draw_circle(center.x, center.y, radius * 0.3, to_color(def.color));
+            }
+            if self.game.called_ball == Some(number) {
+                draw_circle_lines(center.x, center.y, radius * 1.3, 2.0, game_colors::CUE_TIP);
+            }
+        }
     }
     
-    fn draw_main_message(&self) {
+    // Os avisos da última jogada numa linha só; se não couber, a fonte diminui
+    pub fn draw_main_message(&self) {
         let screen_w = screen_width();
-        let msg_font_size = (screen_height() * 0.035).max(18.0) as u16;
-        let msg_text_dims = measure_text(&self.message, Some(&self.font), msg_font_size, 1.0);
+        let message = self.notices.iter().map(|notice| notice.text(&self.game)).collect::<Vec<_>>().join(" ");
+        let mut msg_font_size = (screen_height() * 0.035).max(18.0) as u16;
+        let mut msg_text_dims = measure_text(&message, self.font.as_ref(), msg_font_size, 1.0);
+        if msg_text_dims.width > screen_w * 0.95 {
+            msg_font_size = ((msg_font_size as f32 * screen_w * 0.95 / msg_text_dims.width) as u16).max(10);
+            msg_text_dims = measure_text(&message, self.font.as_ref(), msg_font_size, 1.0);
+        }
         let msg_y_pos = self.table_offset.y / 2.0 - msg_text_dims.height / 2.0;
         
-        draw_text_ex(&self.message, screen_w / 2.0 - msg_text_dims.width / 2.0, msg_y_pos.max(msg_font_size as f32 * 0.5), TextParams {
-            font: Some(&self.font), font_size: msg_font_size, color: WHITE, ..Default::default()
+        draw_text_ex(&message, screen_w / 2.0 - msg_text_dims.width / 2.0, msg_y_pos.max(msg_font_size as f32 * 0.5), TextParams {
+            font: self.font.as_ref(), font_size: msg_font_size, color: WHITE, ..Default::default()
         });
     }
 }
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+// Catálogos de mensagens. Cada idioma é um arquivo em locales/ com uma mensagem
+// por linha (`chave = texto`); `{nome}` no texto é trocado pelo parâmetro de mesmo
+// nome. Mensagens com plural têm uma linha por forma (`chave.one`, `chave.other`)
+// e escolhem a forma pelo parâmetro `count`.
+//
+// O núcleo não traduz nada: entrega enums e erros, e os rótulos deles saem
+// daqui e de labels.rs.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Portuguese,
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Portuguese, Language::English];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Portuguese => "pt",
+            Language::English => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Self::ALL.into_iter().find(|language| code.starts_with(language.code()))
+    }
+
+    pub fn next(self) -> Language {
+        let idx = Self::ALL.iter().position(|&language| language == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    // Forma do plural para a quantidade: no português 0 e 1 são singular
+    fn plural_form(self, count: i64) -> &'static str {
+        match self {
+            Language::Portuguese if count == 0 || count.abs() == 1 => "one",
+            Language::English if count.abs() == 1 => "one",
+            _ => "other",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::Portuguese => include_str!("../locales/pt.txt"),
+            Language::English => include_str!("../locales/en.txt"),
+        }
+    }
+}
+
+// O nome de cada idioma aparece nele mesmo, para quem não lê o idioma atual achar o seu
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Language::Portuguese => write!(f, "Português"),
+            Language::English => write!(f, "English"),
+        }
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+static CATALOGS: OnceLock<Vec<Catalog>> = OnceLock::new();
+
+pub fn language() -> Language {
+    Language::ALL[LANGUAGE.load(Ordering::Relaxed) as usize]
+}
+
+pub fn set_language(language: Language) {
+    let idx = Language::ALL.iter().position(|&l| l == language).unwrap_or(0);
+    LANGUAGE.store(idx as u8, Ordering::Relaxed);
+}
+
+struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    fn parse(source: &str) -> Catalog {
+        let messages = source.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, text)| (key.trim().to_string(), text.trim().to_string()))
+            .collect();
+        Catalog { messages }
+    }
+
+    fn get(&self, key: &str, language: Language, count: Option<i64>) -> Option<&str> {
+        if let Some(count) = count
+            && let Some(text) = self.messages.get(&format!("{}.{}", key, language.plural_form(count)))
+        {
+            return Some(text);
+        }
+        self.messages.get(key).map(String::as_str)
+    }
+}
+
+fn catalog(language: Language) -> &'static Catalog {
+    let catalogs = CATALOGS.get_or_init(|| Language::ALL.iter().map(|language| Catalog::parse(language.source())).collect());
+    &catalogs[Language::ALL.iter().position(|&l| l == language).unwrap_or(0)]
+}
+
+// Mensagem `key` no idioma atual com os parâmetros trocados. Falta no idioma cai
+// para o português (o catálogo completo) e, por fim, para a própria chave.
+pub fn tr(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let count = args.iter()
+        .find(|(name, _)| *name == "count")
+        .and_then(|(_, value)| value.to_string().parse::<i64>().ok());
+    let language = language();
+    let text = catalog(language).get(key, language, count)
+        .or_else(|| catalog(Language::Portuguese).get(key, Language::Portuguese, count))
+        .unwrap_or(key);
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &rest[start + 1..start + len];
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(&value.to_string()),
+            None => out.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// tr!("chave") ou tr!("chave", player = id, count = n)
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::tr($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::tr($key, &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
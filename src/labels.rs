@@ -0,0 +1,201 @@
+use potshot_core::Game;
+use potshot_core::clock::{format_clock, TimeControl};
+use potshot_core::config::{SNOOKER_BLACK, SNOOKER_BLUE, SNOOKER_BROWN, SNOOKER_GREEN, SNOOKER_PINK, SNOOKER_YELLOW};
+use potshot_core::house_rules::{HouseRules, HouseRulesPreset};
+use potshot_core::match_play::{BreakRotation, MatchSettings};
+use potshot_core::physics::PhysicsProfile;
+use potshot_core::rack::RackLooseness;
+use potshot_core::save::{SaveError, SAVE_VERSION};
+use potshot_core::script::ScriptError;
+use potshot_core::table::TableError;
+use potshot_core::types::{Foul, GameVariant, PlayerGroup, PlayerId, SnookerPhase};
+
+use crate::tr;
+
+// Texto na tela dos tipos do núcleo, que só conhece enums: cada um vira a
+// mensagem do catálogo no idioma atual
+pub trait Label {
+    fn label(&self) -> String;
+}
+
+impl Label for PlayerId {
+    fn label(&self) -> String {
+        tr!("player.name", number = self.0 + 1)
+    }
+}
+
+// Rótulo curto do jogador usado no HUD
+pub fn short_label(player: PlayerId) -> String {
+    tr!("player.short", number = player.0 + 1)
+}
+
+impl Label for PlayerGroup {
+    fn label(&self) -> String {
+        match self {
+            PlayerGroup::Solids => tr!("group.solids"),
+            PlayerGroup::Stripes => tr!("group.stripes"),
+            PlayerGroup::Reds => tr!("group.reds"),
+            PlayerGroup::Yellows => tr!("group.yellows"),
+            PlayerGroup::Range(low, high) => format!("{}-{}", low, high),
+            PlayerGroup::Undecided => tr!("group.undecided"),
+            PlayerGroup::EightBall => tr!("group.eight_ball"),
+        }
+    }
+}
+
+impl Label for GameVariant {
+    fn label(&self) -> String {
+        match self {
+            GameVariant::EightBall => tr!("variant.eight_ball"),
+            GameVariant::StraightPool => tr!("variant.straight_pool"),
+            GameVariant::Snooker => tr!("variant.snooker"),
+            GameVariant::Blackball => tr!("variant.blackball"),
+            GameVariant::ThreeCushion => tr!("variant.three_cushion"),
+            GameVariant::Cutthroat => tr!("variant.cutthroat"),
+            GameVariant::Scripted => tr!("variant.scripted"),
+        }
+    }
+}
+
+impl Label for Foul {
+    fn label(&self) -> String {
+        match self {
+            Foul::Scratch => tr!("foul.scratch"),
+            Foul::NoContact => tr!("foul.no_contact"),
+            Foul::NoRail => tr!("foul.no_rail"),
+            Foul::IllegalBreak => tr!("foul.illegal_break"),
+            Foul::WrongBallFirst => tr!("foul.wrong_ball_first"),
+            Foul::WrongBallPotted => tr!("foul.wrong_ball_potted"),
+            Foul::ShotClock => tr!("foul.shot_clock"),
+        }
+    }
+}
+
+impl Label for PhysicsProfile {
+    fn label(&self) -> String {
+        match self {
+            PhysicsProfile::Standard => tr!("physics.standard"),
+            PhysicsProfile::Fast => tr!("physics.fast"),
+            PhysicsProfile::Heavy => tr!("physics.heavy"),
+        }
+    }
+}
+
+impl Label for RackLooseness {
+    fn label(&self) -> String {
+        match self {
+            RackLooseness::Tight => tr!("rack.tight"),
+            RackLooseness::Slight => tr!("rack.slight"),
+            RackLooseness::Loose => tr!("rack.loose"),
+        }
+    }
+}
+
+impl Label for BreakRotation {
+    fn label(&self) -> String {
+        match self {
+            BreakRotation::WinnerBreaks => tr!("rotation.winner_breaks"),
+            BreakRotation::Alternate => tr!("rotation.alternate"),
+            BreakRotation::LoserBreaks => tr!("rotation.loser_breaks"),
+        }
+    }
+}
+
+impl Label for MatchSettings {
+    fn label(&self) -> String {
+        if self.is_single_rack() {
+            tr!("match.single_rack")
+        } else {
+            tr!("match.race", count = self.race_to)
+        }
+    }
+}
+
+impl Label for TimeControl {
+    fn label(&self) -> String {
+        match (self.shot_seconds, self.match_seconds) {
+            (None, None) => tr!("clock.off"),
+            (Some(shot), None) => format!("{:.0}s", shot),
+            (None, Some(total)) => format_clock(total),
+            (Some(shot), Some(total)) => format!("{:.0}s + {}", shot, format_clock(total)),
+        }
+    }
+}
+
+impl Label for HouseRules {
+    fn label(&self) -> String {
+        match self.preset {
+            Some(HouseRulesPreset::Classic) => tr!("house_rules.classic"),
+            Some(HouseRulesPreset::Official) => tr!("house_rules.official"),
+            Some(HouseRulesPreset::Bar) => tr!("house_rules.bar"),
+            None => tr!("house_rules.custom"),
+        }
+    }
+}
+
+impl Label for TableError {
+    fn label(&self) -> String {
+        match self {
+            TableError::Line(line) => tr!("error.table_line", line = line),
+            TableError::Value { key, value } => tr!("error.table_value", key = key, value = value),
+            TableError::Missing(key) => tr!("error.table_missing", key = key),
+            TableError::Pocket(pos) => tr!("error.table_pocket", x = pos.x, y = pos.y),
+        }
+    }
+}
+
+impl Label for ScriptError {
+    fn label(&self) -> String {
+        match self {
+            ScriptError::Engine(error) => error.clone(),
+            ScriptError::Missing(function) => tr!("error.script_missing", function = function),
+            ScriptError::Player(player) => tr!("error.script_player", player = player),
+            ScriptError::Group(group) => tr!("error.script_group", group = group),
+            ScriptError::Foul(foul) => tr!("error.script_foul", foul = foul),
+            ScriptError::Rack(found) => tr!("error.script_rack", found = found),
+            ScriptError::Disabled => tr!("error.script_disabled"),
+        }
+    }
+}
+
+impl Label for SaveError {
+    fn label(&self) -> String {
+        match self {
+            SaveError::Format(error) => tr!("error.save_invalid", error = error),
+            SaveError::UnsupportedVersion(version) => tr!("error.save_version", found = version, expected = SAVE_VERSION),
+        }
+    }
+}
+
+pub fn snooker_colour_name(number: u8) -> String {
+    let key = match number {
+        SNOOKER_YELLOW => "snooker.yellow",
+        SNOOKER_GREEN => "snooker.green",
+        SNOOKER_BROWN => "snooker.brown",
+        SNOOKER_BLUE => "snooker.blue",
+        SNOOKER_PINK => "snooker.pink",
+        SNOOKER_BLACK => "snooker.black",
+        _ => "snooker.red",
+    };
+    tr!(key)
+}
+
+// Bola cantada nos avisos: o número, ou a cor no snooker
+pub fn ball_label(game: &Game, number: u8) -> String {
+    match game.variant {
+        GameVariant::Snooker => snooker_colour_name(number).to_lowercase(),
+        _ => number.to_string(),
+    }
+}
+
+pub fn snooker_phase_label(game: &Game) -> String {
+    let label = match game.snooker_phase {
+        SnookerPhase::Red => tr!("snooker.red"),
+        SnookerPhase::Colour => match game.called_ball {
+            Some(colour) => snooker_colour_name(colour),
+            None => tr!("snooker.colour_call"),
+        },
+        SnookerPhase::Clearance(colour) => snooker_colour_name(colour),
+    };
+    if game.free_ball { tr!("snooker.free_ball", ball = label) } else { label }
+}
@@ -1,19 +1,32 @@
-
 use macroquad::prelude::*;
 
-mod game;
-mod types;
-mod constants;
+mod app;
+mod assets;
 mod colors;
-mod config;
-mod ball;
-mod pocket;
 mod cue;
 mod drawing;
-mod physics;
-mod rules;
+mod i18n;
+mod labels;
+mod menus;
+mod notices;
+mod save;
+mod settings;
+
+use app::App;
 
-use game::Game;
+// Telas do jogo. Só a cena atual recebe input, e a troca fica para o fim do
+// quadro, então um clique nunca vale em duas telas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scene {
+    MainMenu,
+    Setup,
+    // Regras da casa do bola 8, uma por uma; abre da nova partida e volta para ela
+    HouseRules,
+    Playing,
+    Paused,
+    Results,
+    Settings,
+}
 
 fn window_conf() -> Conf {
     Conf {
@@ -26,22 +39,59 @@ fn window_conf() -> Conf {
 
 #[macroquad::main(window_conf)]
 async fn main() {
-    let mut game_instance = Game::new().await;
+    // Fechar a janela (ou o botão voltar no Android) passa pelo loop para salvar antes
+    prevent_quit();
+    let mut app = App::new().await;
+    let mut scene = Scene::MainMenu;
+    // potshot --replay <arquivo> abre direto num replay gravado
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(idx) = args.iter().position(|arg| arg == "--replay")
+        && let Some(path) = args.get(idx + 1)
+    {
+        app.open_replay_file(std::path::Path::new(path));
+        scene = Scene::Playing;
+    }
     let mut last_screen_width = screen_width();
     let mut last_screen_height = screen_height();
 
     loop {
+        // A mesa tem medidas próprias, então mudar o tamanho da tela só refaz o layout
         if screen_width() != last_screen_width || screen_height() != last_screen_height {
-            game_instance.resize_and_init(); // Assume Game has this public method
+            app.layout();
             last_screen_width = screen_width();
             last_screen_height = screen_height();
         }
 
-        game_instance.process_input();
-        game_instance.update();
-        game_instance.render();
+        if is_quit_requested() || app.quit {
+            app.autosave();
+            break;
+        }
+
+        app.watch_settings();
+        match scene {
+            Scene::Playing => {
+                app.process_input();
+                app.update();
+                if app.match_finished() {
+                    app.change_scene(Scene::Results);
+                }
+                app.render();
+            }
+            Scene::Paused | Scene::Results => {
+                app.process_menu_input(scene);
+                app.render();
+                app.draw_menu(scene);
+            }
+            Scene::MainMenu | Scene::Setup | Scene::HouseRules | Scene::Settings => {
+                app.process_menu_input(scene);
+                app.draw_menu(scene);
+            }
+        }
+        if let Some(next) = app.scene_change.take() {
+            app.enter_scene(scene, next);
+            scene = next;
+        }
 
         next_frame().await
     }
 }
-
@@ -0,0 +1,429 @@
+use macroquad::prelude::*;
+
+use potshot_core::Game;
+use potshot_core::clock::TimeControl;
+use potshot_core::house_rules::{HouseRule, HouseRules};
+use potshot_core::match_play::MatchSettings;
+use potshot_core::rack::RackLooseness;
+use potshot_core::replay::Replay;
+use potshot_core::script::ScriptedVariant;
+use potshot_core::table::TableSpec;
+use potshot_core::types::{GameState, GameVariant};
+use crate::i18n;
+use crate::tr;
+use crate::labels::Label;
+use crate::Scene;
+use crate::app::App;
+use crate::colors::game_colors;
+use crate::notices::Notice;
+use crate::save::{read_save, settings_path};
+
+// Opções da tela de nova partida. Ficam fora do Game até o jogador confirmar,
+// para a partida em andamento não mudar enquanto ele escolhe.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GameSetup {
+    pub variant: GameVariant,
+    // Nome da variante com script, quando variant é Scripted
+    pub script: Option<String>,
+    // Nome da mesa; sem ele vale a padrão da modalidade
+    pub table: Option<String>,
+    pub player_count: usize,
+    pub match_settings: MatchSettings,
+    pub house_rules: HouseRules,
+    pub time_control: TimeControl,
+    pub looseness: RackLooseness,
+}
+
+impl Default for GameSetup {
+    fn default() -> Self {
+        GameSetup {
+            variant: GameVariant::EightBall,
+            script: None,
+            table: None,
+            player_count: 2,
+            match_settings: MatchSettings::SINGLE_RACK,
+            house_rules: HouseRules::default(),
+            time_control: TimeControl::OFF,
+            looseness: RackLooseness::default(),
+        }
+    }
+}
+
+impl GameSetup {
+    // Variante com script que não foi carregada (arquivo apagado, erro no script)
+    // vira bola 8
+    pub fn apply(&self, game: &mut Game, variants: &[ScriptedVariant], tables: &[TableSpec]) {
+        let script = self.script.as_ref().and_then(|name| variants.iter().find(|variant| &variant.name == name));
+        game.variant = match (self.variant, script) {
+            (GameVariant::Scripted, None) => GameVariant::EightBall,
+            (variant, _) => variant,
+        };
+        game.script = script.filter(|_| game.variant == GameVariant::Scripted).cloned();
+        game.table = self.table_in(game.variant, tables).cloned().unwrap_or_else(|| TableSpec::standard(game.variant));
+        game.player_count = self.player_count;
+        game.match_settings = self.match_settings;
+        game.house_rules = self.house_rules;
+        game.time_control = self.time_control;
+        game.rack_options.looseness = self.looseness;
+    }
+
+    // Modalidades embutidas e depois as com script, uma por uma, voltando à bola 8
+    fn next_variant(&mut self, variants: &[ScriptedVariant]) {
+        let position = self.script.as_ref()
+            .filter(|_| self.variant == GameVariant::Scripted)
+            .and_then(|name| variants.iter().position(|variant| &variant.name == name));
+        let next_script = match position {
+            Some(idx) => variants.get(idx + 1),
+            None if self.variant.next() == GameVariant::EightBall && self.variant != GameVariant::Scripted => variants.first(),
+            None => None,
+        };
+        match next_script {
+            Some(variant) => {
+                self.variant = GameVariant::Scripted;
+                self.script = Some(variant.name.clone());
+            }
+            None => {
+                self.variant = self.variant.next();
+                self.script = None;
+            }
+        }
+        let counts = self.variant.player_counts();
+        self.player_count = self.player_count.clamp(*counts.start(), *counts.end());
+        self.table = None;
+    }
+
+    // Mesa escolhida (ou a padrão) entre as que servem à modalidade
+    fn table_in<'a>(&self, variant: GameVariant, tables: &'a [TableSpec]) -> Option<&'a TableSpec> {
+        let name = self.table.as_deref().unwrap_or(TableSpec::standard_name(variant));
+        tables.iter().find(|table| table.name == name && table.fits(variant))
+    }
+
+    // Próxima mesa que serve à modalidade, voltando à primeira
+    fn next_table(&mut self, tables: &[TableSpec]) {
+        let fitting: Vec<&TableSpec> = tables.iter().filter(|table| table.fits(self.variant)).collect();
+        let current = self.table_in(self.variant, tables)
+            .and_then(|table| fitting.iter().position(|other| other.name == table.name));
+        let next = current.map_or(0, |idx| (idx + 1) % fitting.len().max(1));
+        if let Some(table) = fitting.get(next) {
+            self.table = Some(table.name.clone());
+        }
+    }
+
+    fn variant_label(&self) -> String {
+        match &self.script {
+            Some(name) if self.variant == GameVariant::Scripted => format!("{} ({})", self.variant.label(), name),
+            _ => self.variant.label(),
+        }
+    }
+
+    fn next_player_count(&mut self) {
+        let counts = self.variant.player_counts();
+        self.player_count = if self.player_count >= *counts.end() { *counts.start() } else { self.player_count + 1 };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuAction {
+    Continue,
+    NewGame,
+    LoadGame,
+    Settings,
+    Quit,
+    Variant,
+    Players,
+    Table,
+    Match,
+    AllowUndo,
+    HouseRules,
+    HousePreset,
+    HouseRule(HouseRule),
+    Clock,
+    Rack,
+    SaveDefaults,
+    Start,
+    Back,
+    Resume,
+    Restart,
+    MainMenu,
+    Rematch,
+    WatchReplay,
+    Language,
+    Theme,
+    Physics,
+    AimAssist,
+    Volume,
+    SaveSettings,
+}
+
+impl App {
+    // O resultado aparece quando a partida termina na mesa (não no replay)
+    pub fn match_finished(&self) -> bool {
+        self.replay.is_none() && self.game.game_state() == GameState::MatchOver
+    }
+
+    // Título e itens da cena, de cima para baixo
+    fn menu_items(&self, scene: Scene) -> (String, Vec<(String, MenuAction)>) {
+        match scene {
+            Scene::MainMenu => {
+                let mut items = Vec::new();
+                if self.game_started {
+                    items.push((tr!("ui.continue"), MenuAction::Continue));
+                }
+                items.push((tr!("ui.new_match"), MenuAction::NewGame));
+                items.push((tr!("ui.load"), MenuAction::LoadGame));
+                items.push((tr!("ui.settings"), MenuAction::Settings));
+                items.push((tr!("ui.quit"), MenuAction::Quit));
+                ("PotShot".to_string(), items)
+            }
+            Scene::Setup => {
+                let setup = &self.setup;
+                let mut items = vec![(tr!("ui.mode", variant = setup.variant_label()), MenuAction::Variant)];
+                if setup.variant.player_counts().count() > 1 {
+                    items.push((tr!("ui.players", count = setup.player_count), MenuAction::Players));
+                }
+                let table = setup.table_in(setup.variant, &self.tables).cloned().unwrap_or_else(|| TableSpec::standard(setup.variant));
+                items.push((tr!("ui.table", table = table.label(i18n::language().code())), MenuAction::Table));
+                items.push((tr!("ui.match", settings = setup.match_settings.label()), MenuAction::Match));
+                let undo = if setup.match_settings.allow_undo { tr!("ui.on") } else { tr!("ui.off") };
+                items.push((tr!("ui.allow_undo", value = undo), MenuAction::AllowUndo));
+                if setup.variant == GameVariant::EightBall {
+                    items.push((tr!("ui.house_rules", rules = setup.house_rules.label()), MenuAction::HouseRules));
+                }
+                items.push((tr!("ui.clock", clock = setup.time_control.label()), MenuAction::Clock));
+                items.push((tr!("ui.rack", looseness = setup.looseness.label()), MenuAction::Rack));
+                items.push((tr!("ui.save_defaults"), MenuAction::SaveDefaults));
+                items.push((tr!("ui.start"), MenuAction::Start));
+                items.push((tr!("ui.back"), MenuAction::Back));
+                (tr!("menu.setup"), items)
+            }
+            Scene::HouseRules => {
+                let rules = &self.setup.house_rules;
+                let mut items = vec![(tr!("ui.house_rules", rules = rules.label()), MenuAction::HousePreset)];
+                for rule in HouseRule::ALL {
+                    let value = if rules.is_on(rule) { tr!("ui.on") } else { tr!("ui.off") };
+                    items.push((tr!(house_rule_key(rule), value = value), MenuAction::HouseRule(rule)));
+                }
+                items.push((tr!("ui.back"), MenuAction::Back));
+                (tr!("menu.house_rules"), items)
+            }
+            Scene::Paused => (tr!("menu.paused"), vec![
+                (tr!("ui.resume"), MenuAction::Resume),
+                (tr!("ui.reset"), MenuAction::Restart),
+                (tr!("ui.new_match"), MenuAction::NewGame),
+                (tr!("ui.settings"), MenuAction::Settings),
+                (tr!("ui.main_menu"), MenuAction::MainMenu),
+            ]),
+            Scene::Results => {
+                let mut items = vec![(tr!("ui.rematch"), MenuAction::Rematch)];
+                if Replay::of_rack(&self.game).is_some() {
+                    items.push((tr!("ui.watch_replay"), MenuAction::WatchReplay));
+                }
+                items.push((tr!("ui.new_match"), MenuAction::NewGame));
+                items.push((tr!("ui.main_menu"), MenuAction::MainMenu));
+                (tr!("match_over.title"), items)
+            }
+            Scene::Settings => {
+                let draft = &self.settings_draft;
+                (tr!("menu.settings"), vec![
+                    (tr!("ui.language", language = draft.language), MenuAction::Language),
+                    (tr!("ui.theme", theme = draft.theme), MenuAction::Theme),
+                    (tr!("ui.physics", profile = draft.physics.label()), MenuAction::Physics),
+                    (tr!("ui.aim_assist", level = draft.aim_assist), MenuAction::AimAssist),
+                    (tr!("ui.volume", percent = (draft.volume * 100.0).round()), MenuAction::Volume),
+                    (tr!("ui.save"), MenuAction::SaveSettings),
+                    (tr!("ui.back"), MenuAction::Back),
+                ])
+            }
+            Scene::Playing => (String::new(), Vec::new()),
+        }
+    }
+
+    // Botões em coluna no centro da tela, abaixo das linhas de texto da cena. Com
+    // muitos itens os botões encolhem para a coluna caber na tela.
+    fn menu_rects(&self, scene: Scene, count: usize) -> Vec<Rect> {
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+        let width = (screen_w * 0.3).max(220.0).min(screen_w * 0.9);
+        let text_lines = self.menu_text(scene).len() as f32;
+        let top = screen_h * (0.16 + 0.05 * (text_lines + 1.0));
+        let height = (screen_h * 0.06).min((screen_h * 0.97 - top) / (count.max(1) as f32 * 1.35));
+        let gap = height * 0.35;
+        (0..count)
+            .map(|i| Rect::new((screen_w - width) / 2.0, top + i as f32 * (height + gap), width, height))
+            .collect()
+    }
+
+    // Linhas abaixo do título: o resultado, na tela de fim de partida, e as teclas e
+    // o arquivo, nas configurações
+    fn menu_text(&self, scene: Scene) -> Vec<String> {
+        if scene == Scene::Settings {
+            let bindings = &self.settings_draft.bindings;
+            return vec![
+                tr!("settings.bindings", pause = bindings.pause, undo = bindings.undo, redo = bindings.redo),
+                tr!("settings.file", path = settings_path().display()),
+            ];
+        }
+        if scene != Scene::Results { return Vec::new(); }
+        let mut lines = Vec::new();
+        if let Some(winner) = self.game.match_winner() {
+            lines.push(tr!("match_over.winner", player = winner.label()));
+        }
+        if !self.game.match_settings.is_single_rack() {
+            lines.push(tr!(
+                "match_over.score",
+                score = self.game.match_score_text(),
+                settings = self.game.match_settings.label(),
+                rotation = self.game.match_settings.rotation.label(),
+            ));
+        }
+        lines
+    }
+
+    // Clique completo (apertar e soltar no mesmo lugar) numa cena de menu. O toque
+    // começado em outra cena não conta, porque a troca de cena limpa menu_press.
+    fn menu_click(&mut self) -> Option<Vec2> {
+        let pos = touches().first().map_or_else(|| mouse_position().into(), |touch| touch.position);
+        let pressed = is_mouse_button_pressed(MouseButton::Left)
+            || touches().first().is_some_and(|touch| touch.phase == TouchPhase::Started);
+        let released = is_mouse_button_released(MouseButton::Left)
+            || touches().first().is_some_and(|touch| touch.phase == TouchPhase::Ended);
+        if pressed {
+            self.menu_press = Some(pos);
+        } else if released {
+            let start = self.menu_press.take()?;
+            return (start.distance(pos) < 10.0).then_some(pos);
+        }
+        None
+    }
+
+    // Input das cenas de menu; pede a troca de cena quando uma ação leva a outra tela
+    pub fn process_menu_input(&mut self, scene: Scene) {
+        // Fora da partida ninguém mais junta os avisos
+        self.collect_notices();
+        if self.settings.bindings.pause.is_pressed() {
+            match scene {
+                Scene::Paused => self.change_scene(Scene::Playing),
+                Scene::Setup => self.change_scene(Scene::MainMenu),
+                Scene::HouseRules => self.change_scene(Scene::Setup),
+                Scene::Settings => self.change_scene(self.settings_return),
+                _ => {}
+            }
+            return;
+        }
+        let Some(pos) = self.menu_click() else { return; };
+        let (_, items) = self.menu_items(scene);
+        let rects = self.menu_rects(scene, items.len());
+        if let Some(&(_, action)) = items.iter().zip(rects).find(|(_, rect)| rect.contains(pos)).map(|(item, _)| item) {
+            self.menu_action(scene, action);
+        }
+    }
+
+    fn menu_action(&mut self, scene: Scene, action: MenuAction) {
+        match action {
+            MenuAction::Continue | MenuAction::Resume => self.change_scene(Scene::Playing),
+            MenuAction::NewGame => {
+                self.setup = self.settings.game.clone();
+                self.change_scene(Scene::Setup);
+            }
+            MenuAction::LoadGame => match read_save() {
+                Some(Ok(game)) => {
+                    self.replace_game(game);
+                    self.notify(Notice::Loaded);
+                    self.change_scene(Scene::Playing);
+                }
+                Some(Err(e)) => self.notify(Notice::LoadFailed(e)),
+                None => self.notify(Notice::NoSave),
+            },
+            MenuAction::Settings => {
+                self.settings_return = scene;
+                self.change_scene(Scene::Settings);
+            }
+            MenuAction::Quit => self.quit = true,
+            MenuAction::Variant => self.setup.next_variant(&self.variants),
+            MenuAction::Players => self.setup.next_player_count(),
+            MenuAction::Table => self.setup.next_table(&self.tables),
+            MenuAction::Match => self.setup.match_settings = self.setup.match_settings.next(),
+            MenuAction::AllowUndo => self.setup.match_settings.allow_undo = !self.setup.match_settings.allow_undo,
+            MenuAction::HouseRules => self.change_scene(Scene::HouseRules),
+            MenuAction::HousePreset => self.setup.house_rules = self.setup.house_rules.next(),
+            MenuAction::HouseRule(rule) => self.setup.house_rules.toggle(rule),
+            MenuAction::Clock => self.setup.time_control = self.setup.time_control.next(),
+            MenuAction::Rack => self.setup.looseness = self.setup.looseness.next(),
+            MenuAction::SaveDefaults => {
+                let mut settings = self.settings.clone();
+                settings.game = self.setup.clone();
+                self.save_settings(settings);
+            }
+            MenuAction::Start => {
+                self.setup.apply(&mut self.game, &self.variants, &self.tables);
+                self.new_match();
+                self.change_scene(Scene::Playing);
+            }
+            MenuAction::Back if scene == Scene::Settings => self.change_scene(self.settings_return),
+            MenuAction::Back if scene == Scene::HouseRules => self.change_scene(Scene::Setup),
+            MenuAction::Back | MenuAction::MainMenu => self.change_scene(Scene::MainMenu),
+            MenuAction::Restart | MenuAction::Rematch => {
+                self.new_match();
+                self.change_scene(Scene::Playing);
+            }
+            MenuAction::WatchReplay => {
+                if let Some(replay) = Replay::of_rack(&self.game) {
+                    self.enter_replay(replay);
+                    self.change_scene(Scene::Playing);
+                }
+            }
+            MenuAction::Language => self.settings_draft.language = self.settings_draft.language.next(),
+            MenuAction::Theme => self.settings_draft.theme = self.settings_draft.theme.next(),
+            MenuAction::Physics => self.settings_draft.physics = self.settings_draft.physics.next(),
+            MenuAction::AimAssist => self.settings_draft.aim_assist = self.settings_draft.aim_assist.next(),
+            MenuAction::Volume => self.settings_draft.next_volume(),
+            MenuAction::SaveSettings => {
+                self.save_settings(self.settings_draft.clone());
+                self.change_scene(self.settings_return);
+            }
+        }
+    }
+
+    // Menu principal e nova partida cobrem a tela; pausa e resultado ficam por cima da mesa
+    pub fn draw_menu(&self, scene: Scene) {
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+        if matches!(scene, Scene::MainMenu | Scene::Setup | Scene::HouseRules | Scene::Settings) {
+            clear_background(Color::new(0.05, 0.05, 0.07, 1.0));
+            self.draw_main_message();
+        } else {
+            draw_rectangle(0.0, 0.0, screen_w, screen_h, game_colors::OVERLAY_BG);
+        }
+
+        let (title, items) = self.menu_items(scene);
+        let title_size = (screen_h * 0.06).max(24.0) as u16;
+        let text_size = (screen_h * 0.03).max(16.0) as u16;
+        let mut y = screen_h * 0.16;
+        let mut lines = vec![(title, title_size)];
+        lines.extend(self.menu_text(scene).into_iter().map(|line| (line, text_size)));
+        for (text, size) in &lines {
+            let dims = measure_text(text, self.font.as_ref(), *size, 1.0);
+            draw_text_ex(text, screen_w / 2.0 - dims.width / 2.0, y, TextParams {
+                font: self.font.as_ref(), font_size: *size, color: game_colors::HUD_TEXT_COLOR, ..Default::default()
+            });
+            y += screen_h * 0.05;
+        }
+        for ((label, _), rect) in items.iter().zip(self.menu_rects(scene, items.len())) {
+            self.draw_button(rect, label);
+        }
+    }
+}
+
+fn house_rule_key(rule: HouseRule) -> &'static str {
+    match rule {
+        HouseRule::ScratchOnEightLoses => "house_rule.scratch_on_eight_loses",
+        HouseRule::EightOnBreakWins => "house_rule.eight_on_break_wins",
+        HouseRule::OpenTableAfterBreak => "house_rule.open_table_after_break",
+        HouseRule::OpponentBallIsFoul => "house_rule.opponent_ball_is_foul",
+        HouseRule::BallInHandAnywhere => "house_rule.ball_in_hand_anywhere",
+        HouseRule::CallTheEight => "house_rule.call_the_eight",
+        HouseRule::ChooseGroupAfterMixedBreak => "house_rule.choose_group_after_mixed_break",
+        HouseRule::ThreeFoulLoss => "house_rule.three_foul_loss",
+    }
+}
@@ -0,0 +1,146 @@
+use potshot_core::Game;
+use potshot_core::notification::{LossReason, Notification};
+use potshot_core::types::PlacementArea;
+use crate::tr;
+use crate::labels::{ball_label, Label};
+
+// Avisos da faixa acima da mesa: os que o jogo enfileira e os do próprio app.
+// Guardam o acontecimento e não o texto, então trocar o idioma vale na hora.
+#[derive(Debug, Clone)]
+pub enum Notice {
+    Game(Notification),
+    Resumed,
+    Saved,
+    SaveFailed(String),
+    Loaded,
+    LoadFailed(String),
+    NoSave,
+    ReplayFailed(String),
+    SeedFixed(u32),
+    SeedSet(u32),
+    SeedEditing(u32),
+    SeedFree,
+    ShotUndone,
+    ShotRedone,
+    SettingsSaved,
+    SettingsReloaded,
+    SettingsFailed(String),
+    AssetFailed { path: String, error: String },
+    AssetDirMissing(String),
+    ScriptFailed { path: String, error: String },
+    TableFailed { path: String, error: String },
+}
+
+impl Notice {
+    pub fn text(&self, game: &Game) -> String {
+        match self {
+            Notice::Game(notification) => notification_text(notification, game),
+            Notice::Resumed => tr!("app.resumed"),
+            Notice::Saved => tr!("app.saved"),
+            Notice::SaveFailed(e) => tr!("app.save_failed", error = e),
+            Notice::Loaded => tr!("app.loaded"),
+            Notice::LoadFailed(e) => tr!("app.load_failed", error = e),
+            Notice::NoSave => tr!("app.no_save"),
+            Notice::ReplayFailed(e) => tr!("app.replay_failed", error = e),
+            Notice::SeedFixed(seed) => tr!("app.seed_fixed", seed = seed),
+            Notice::SeedSet(seed) => tr!("app.seed_set", seed = seed),
+            Notice::SeedEditing(seed) => tr!("app.seed_editing", seed = seed),
+            Notice::SeedFree => tr!("app.seed_free"),
+            Notice::ShotUndone => tr!("app.shot_undone"),
+            Notice::ShotRedone => tr!("app.shot_redone"),
+            Notice::SettingsSaved => tr!("app.settings_saved"),
+            Notice::SettingsReloaded => tr!("app.settings_reloaded"),
+            Notice::SettingsFailed(e) => tr!("app.settings_failed", error = e),
+            Notice::AssetFailed { path, error } => tr!("error.font", path = path, error = error),
+            Notice::AssetDirMissing(path) => tr!("app.asset_dir_missing", path = path),
+            Notice::ScriptFailed { path, error } => tr!("app.script_failed", path = path, error = error),
+            Notice::TableFailed { path, error } => tr!("app.table_failed", path = path, error = error),
+        }
+    }
+}
+
+fn notification_text(notification: &Notification, game: &Game) -> String {
+    match *notification {
+        Notification::RackStarted { rack: 1, breaker } => tr!("notice.rack_started_first", player = breaker.label()),
+        Notification::RackStarted { rack, breaker } => tr!("notice.rack_started", rack = rack, player = breaker.label()),
+        Notification::BallsMoving => tr!("notice.balls_moving"),
+        Notification::ShotTooWeak { player } => tr!("notice.shot_too_weak", player = player.label()),
+        Notification::CueBallOutsideArea(PlacementArea::Kitchen) => tr!("notice.outside_kitchen"),
+        Notification::CueBallOutsideArea(PlacementArea::D) => tr!("notice.outside_d"),
+        Notification::CueBallOutsideArea(PlacementArea::Anywhere) => tr!("notice.outside_table"),
+        Notification::CueBallOverlaps => tr!("notice.cue_ball_overlaps"),
+        Notification::CueBallPlaced { player } => tr!("notice.cue_ball_placed", player = player.label()),
+        Notification::BallCalled { player, ball } => tr!("notice.ball_called", player = player.label(), ball = ball_label(game, ball)),
+        Notification::EightPocketCalled { player } => tr!("notice.eight_pocket_called", player = player.label()),
+        Notification::EightPocketRequired { player } => tr!("notice.eight_pocket_required", player = player.label()),
+
+        Notification::TurnChanged { player } => tr!("notice.turn_changed", player = player.label()),
+        Notification::PlaysAgain { player } => tr!("notice.plays_again", player = player.label()),
+        Notification::BallInHand { player, area } => match area {
+            PlacementArea::Kitchen => tr!("notice.ball_in_hand_kitchen", player = player.label()),
+            PlacementArea::D => tr!("notice.ball_in_hand_d", player = player.label()),
+            PlacementArea::Anywhere => tr!("notice.ball_in_hand_anywhere", player = player.label()),
+        },
+
+        Notification::Foul { player, reason } => tr!("notice.foul", player = player.label(), reason = reason.label()),
+        Notification::FoulWarning { player } => tr!("notice.foul_warning", player = player.label()),
+        Notification::ThirdFoul { player } => tr!("notice.third_foul", player = player.label()),
+        Notification::GoodShot => tr!("notice.good_shot"),
+        Notification::NothingPotted => tr!("notice.nothing_potted"),
+        Notification::WrongBallPotted => tr!("notice.wrong_ball_potted"),
+        Notification::NoOwnBall => tr!("notice.no_own_ball"),
+        Notification::OpenTable { after_break: false } => tr!("notice.open_table"),
+        Notification::OpenTable { after_break: true } => tr!("notice.open_table_after_break"),
+        Notification::GroupAssigned { player, group } => tr!("notice.group_assigned", player = player.label(), group = group.label()),
+        Notification::GroupChoice { player } => tr!("notice.group_choice", player = player.label()),
+        Notification::OnTheEight { player } => tr!("notice.on_the_eight", player = player.label()),
+        Notification::EightRespotted => tr!("notice.eight_respotted"),
+        Notification::EightOnBreak => tr!("notice.eight_on_break"),
+        Notification::IllegalBreak { rail_balls, chooser } => tr!("notice.illegal_break", count = rail_balls, player = chooser.label()),
+        Notification::Reracked { breaker } => tr!("notice.reracked", player = breaker.label()),
+        Notification::TableAccepted { player } => tr!("notice.table_accepted", player = player.label()),
+
+        Notification::Scored { player, points } => tr!("notice.scored", player = player.label(), count = points),
+        Notification::PointsLost { player, points } => tr!("notice.points_lost", player = player.label(), count = points),
+        Notification::PointsAwarded { player, points } => tr!("notice.points_awarded", player = player.label(), count = points),
+        Notification::NoPoints => tr!("notice.no_points"),
+        Notification::LegalBreak => tr!("notice.legal_break"),
+        Notification::NewRack => tr!("notice.new_rack"),
+        Notification::CaromPoint { player, score } => tr!("notice.carom_point", player = player.label(), score = score),
+
+        Notification::BlackOnBreak { breaker } => tr!("notice.black_on_break", player = breaker.label()),
+        Notification::TwoShots { player } => tr!("notice.two_shots", player = player.label()),
+        Notification::SecondVisit { player } => tr!("notice.second_visit", player = player.label()),
+        Notification::FreeBall { player } => tr!("notice.free_ball", player = player.label()),
+        Notification::SnookerTie => tr!("notice.snooker_tie"),
+
+        Notification::LagStarted { player } => tr!("notice.lag_started", player = player.label()),
+        Notification::LagTurn { player } => tr!("notice.lag_turn", player = player.label()),
+        Notification::LagWon { player } => tr!("notice.lag_won", player = player.label()),
+        Notification::LagRepeated => tr!("notice.lag_repeated"),
+        Notification::ExtensionUsed { player, seconds } => tr!("notice.extension_used", player = player.label(), seconds = seconds),
+        Notification::ShotClockExpired { player } => tr!("notice.shot_clock_expired", player = player.label()),
+
+        Notification::Eliminated { player, reason } => tr!(&format!("notice.eliminated.{}", loss_key(reason)), player = player.label()),
+        Notification::Lost { player, reason } => tr!(&format!("notice.lost.{}", loss_key(reason)), player = player.label()),
+        Notification::Won { player } => tr!("notice.won", player = player.label()),
+        Notification::FrameWon { player, high, low } => tr!("notice.frame_won", player = player.label(), high = high, low = low),
+        Notification::ScriptError(ref error) => tr!("notice.script_error", error = error.label()),
+        // collect_notices manda para o log antes de chegar aqui
+        Notification::InvalidTransition { from, to } => format!("{:?} -> {:?}", from, to),
+    }
+}
+
+fn loss_key(reason: LossReason) -> &'static str {
+    match reason {
+        LossReason::ThirdFoul => "third_foul",
+        LossReason::OutOfTime => "out_of_time",
+        LossReason::NoBallsLeft => "no_balls_left",
+        LossReason::EightAndScratch => "eight_and_scratch",
+        LossReason::EightOnOpenTable => "eight_on_open_table",
+        LossReason::EightTooEarly => "eight_too_early",
+        LossReason::EightWrongPocket => "eight_wrong_pocket",
+        LossReason::BlackIllegal => "black_illegal",
+        LossReason::Rules => "rules",
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use potshot_core::Game;
+use potshot_core::replay::Replay;
+use potshot_core::save::{load_game, load_replay, save_game, save_replay};
+use crate::labels::Label;
+use crate::settings::Settings;
+
+// Arquivo da partida em andamento, no diretório de dados da plataforma.
+// Sem esse diretório (algumas plataformas móveis) fica no diretório atual.
+fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join("potshot"))
+        .unwrap_or_default()
+}
+
+pub fn save_path() -> PathBuf {
+    data_dir().join("save.json")
+}
+
+// Configurações no diretório de configuração da plataforma, com o mesmo recurso
+// ao diretório atual
+pub fn settings_path() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("potshot"))
+        .unwrap_or_default()
+        .join("settings.json")
+}
+
+fn write_file(path: &Path, text: String) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn write_save(game: &Game) -> Result<(), String> {
+    let text = save_game(game).map_err(|e| e.label())?;
+    write_file(&save_path(), text)
+}
+
+// None quando não há save; Some(Err) quando o arquivo existe mas não serve
+pub fn read_save() -> Option<Result<Game, String>> {
+    let path = save_path();
+    let text = std::fs::read_to_string(&path).ok()?;
+    Some(load_game(&text).map_err(|e| format!("{}: {}", path.display(), e.label())))
+}
+
+// Partida terminada não tem o que retomar; sem save não há o que apagar
+pub fn remove_save() -> Result<(), String> {
+    let path = save_path();
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("{}: {}", path.display(), e)),
+        _ => Ok(()),
+    }
+}
+
+pub fn write_settings(settings: &Settings) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    write_file(&settings_path(), text)
+}
+
+// Como read_save: None sem arquivo, Some(Err) com arquivo inválido
+pub fn read_settings() -> Option<Result<Settings, String>> {
+    let path = settings_path();
+    let text = std::fs::read_to_string(&path).ok()?;
+    let settings = serde_json::from_str::<Settings>(&text).map(|mut settings| {
+        settings.volume = settings.volume.clamp(0.0, 1.0);
+        settings
+    });
+    Some(settings.map_err(|e| format!("{}: {}", path.display(), e)))
+}
+
+// Replays guardados; passando disso os mais antigos saem
+const MAX_REPLAYS: usize = 50;
+
+// Cada rack terminado vira um arquivo em replays/, com o horário no nome. Dois racks
+// no mesmo milissegundo ganham um sufixo em vez de um apagar o outro.
+pub fn write_replay(replay: &Replay) -> Result<PathBuf, String> {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let dir = data_dir().join("replays");
+    let mut path = dir.join(format!("replay-{}.json", millis));
+    let mut copy = 1;
+    while path.exists() {
+        path = dir.join(format!("replay-{}-{}.json", millis, copy));
+        copy += 1;
+    }
+    let text = save_replay(replay).map_err(|e| e.label())?;
+    write_file(&path, text)?;
+    prune_replays(&dir);
+    Ok(path)
+}
+
+// Apaga os replays mais antigos além de MAX_REPLAYS. Um arquivo que não sai agora
+// sai na próxima gravação, então os erros ficam de fora.
+fn prune_replays(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return; };
+    let mut replays: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("replay-") && name.ends_with(".json")
+        })
+        .map(|entry| {
+            let modified = entry.metadata().and_then(|meta| meta.modified()).unwrap_or(UNIX_EPOCH);
+            (modified, entry.path())
+        })
+        .collect();
+    if replays.len() <= MAX_REPLAYS { return; }
+    replays.sort();
+    for (_, path) in &replays[..replays.len() - MAX_REPLAYS] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+pub fn read_replay(path: &Path) -> Result<Replay, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    load_replay(&text).map_err(|e| format!("{}: {}", path.display(), e.label()))
+}
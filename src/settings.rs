@@ -0,0 +1,213 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use potshot_core::physics::PhysicsProfile;
+use crate::i18n::Language;
+use crate::tr;
+use crate::colors::Theme;
+use crate::menus::GameSetup;
+
+// Preferências do jogador, em settings.json no diretório de configuração. Campos
+// que faltam no arquivo ficam com o padrão, então um arquivo antigo (ou escrito à
+// mão com só uma linha) continua valendo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Language,
+    pub theme: Theme,
+    // Vale a partir da próxima partida: a física do rack em andamento não muda
+    pub physics: PhysicsProfile,
+    pub aim_assist: AimAssist,
+    // Entre 0 e 1. O jogo ainda não tem sons; o volume fica guardado para eles.
+    pub volume: f32,
+    pub bindings: Bindings,
+    // Opções com que a tela de nova partida abre
+    pub game: GameSetup,
+    // Diretórios de assets por cima dos padrões, o primeiro valendo mais. Lidos ao
+    // abrir o jogo; mudar com o jogo aberto só vale na próxima vez.
+    pub asset_dirs: Vec<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            language: system_language(),
+            theme: Theme::default(),
+            physics: PhysicsProfile::default(),
+            aim_assist: AimAssist::default(),
+            volume: 0.8,
+            bindings: Bindings::default(),
+            game: GameSetup::default(),
+            asset_dirs: Vec::new(),
+        }
+    }
+}
+
+// Idioma do sistema (LC_ALL, LC_MESSAGES ou LANG); fora do português, inglês
+fn system_language() -> Language {
+    ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map_or(Language::Portuguese, |value| Language::from_code(&value).unwrap_or(Language::English))
+}
+
+impl Settings {
+    // Volume em passos de 25%, voltando ao mudo depois do máximo
+    pub fn next_volume(&mut self) {
+        let step = (self.volume * 4.0).round() as u32;
+        self.volume = ((step + 1) % 5) as f32 / 4.0;
+    }
+}
+
+// Quanto da linha de mira aparece enquanto o jogador puxa o taco
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AimAssist {
+    Off,
+    Short,
+    #[default]
+    Full,
+}
+
+impl AimAssist {
+    pub fn next(self) -> AimAssist {
+        match self {
+            AimAssist::Off => AimAssist::Short,
+            AimAssist::Short => AimAssist::Full,
+            AimAssist::Full => AimAssist::Off,
+        }
+    }
+
+    // Comprimento da linha em frações do comprimento da mesa
+    pub fn line_fraction(self) -> f32 {
+        match self {
+            AimAssist::Off => 0.0,
+            AimAssist::Short => 0.25,
+            AimAssist::Full => 1.0,
+        }
+    }
+}
+
+impl std::fmt::Display for AimAssist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AimAssist::Off => write!(f, "{}", tr!("aim_assist.off")),
+            AimAssist::Short => write!(f, "{}", tr!("aim_assist.short")),
+            AimAssist::Full => write!(f, "{}", tr!("aim_assist.full")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bindings {
+    pub pause: Binding,
+    pub undo: Binding,
+    pub redo: Binding,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings {
+            pause: Binding { ctrl: false, key: KeyCode::Escape },
+            undo: Binding { ctrl: true, key: KeyCode::Z },
+            redo: Binding { ctrl: true, key: KeyCode::Y },
+        }
+    }
+}
+
+// Uma tecla, com ou sem Ctrl. No arquivo fica como texto: "Escape", "Ctrl+Z".
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Binding {
+    pub ctrl: bool,
+    pub key: KeyCode,
+}
+
+// Teclas que dá para usar nos atalhos. Os dígitos ficam de fora: editam a semente.
+const KEY_NAMES: [(&str, KeyCode); 43] = [
+    ("A", KeyCode::A), ("B", KeyCode::B), ("C", KeyCode::C), ("D", KeyCode::D),
+    ("E", KeyCode::E), ("F", KeyCode::F), ("G", KeyCode::G), ("H", KeyCode::H),
+    ("I", KeyCode::I), ("J", KeyCode::J), ("K", KeyCode::K), ("L", KeyCode::L),
+    ("M", KeyCode::M), ("N", KeyCode::N), ("O", KeyCode::O), ("P", KeyCode::P),
+    ("Q", KeyCode::Q), ("R", KeyCode::R), ("S", KeyCode::S), ("T", KeyCode::T),
+    ("U", KeyCode::U), ("V", KeyCode::V), ("W", KeyCode::W), ("X", KeyCode::X),
+    ("Y", KeyCode::Y), ("Z", KeyCode::Z),
+    ("F1", KeyCode::F1), ("F2", KeyCode::F2), ("F3", KeyCode::F3), ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5), ("F6", KeyCode::F6), ("F7", KeyCode::F7), ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9), ("F10", KeyCode::F10), ("F11", KeyCode::F11), ("F12", KeyCode::F12),
+    ("Escape", KeyCode::Escape), ("Space", KeyCode::Space), ("Enter", KeyCode::Enter),
+    ("Tab", KeyCode::Tab), ("Pause", KeyCode::Pause),
+];
+
+impl Binding {
+    pub fn is_pressed(&self) -> bool {
+        let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+        ctrl == self.ctrl && is_key_pressed(self.key)
+    }
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let (ctrl, name) = match text.split_once('+') {
+            Some((modifier, name)) if modifier.eq_ignore_ascii_case("ctrl") => (true, name),
+            _ => (false, text.as_str()),
+        };
+        KEY_NAMES.iter()
+            .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name.trim()))
+            .map(|&(_, key)| Binding { ctrl, key })
+            .ok_or_else(|| tr!("error.unknown_key", key = text))
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> String {
+        binding.to_string()
+    }
+}
+
+impl std::fmt::Display for Binding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = KEY_NAMES.iter().find(|(_, key)| *key == self.key).map_or("?", |(name, _)| name);
+        if self.ctrl { write!(f, "Ctrl+{}", name) } else { write!(f, "{}", name) }
+    }
+}
+
+// Olha a data de modificação do arquivo de tempos em tempos, para recarregar as
+// configurações editadas à mão com o jogo aberto
+pub struct SettingsWatch {
+    modified: Option<SystemTime>,
+    next_check: f64,
+}
+
+impl SettingsWatch {
+    const INTERVAL_SECONDS: f64 = 1.0;
+
+    pub fn new(path: &Path) -> Self {
+        SettingsWatch { modified: modified_time(path), next_check: 0.0 }
+    }
+
+    // true quando o arquivo mudou desde a última olhada (ou desde sync)
+    pub fn changed(&mut self, path: &Path) -> bool {
+        let now = get_time();
+        if now < self.next_check { return false; }
+        self.next_check = now + Self::INTERVAL_SECONDS;
+        let modified = modified_time(path);
+        if modified == self.modified { return false; }
+        self.modified = modified;
+        modified.is_some()
+    }
+
+    // Depois que o próprio jogo grava o arquivo, para não recarregar o que acabou de salvar
+    pub fn sync(&mut self, path: &Path) {
+        self.modified = modified_time(path);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}